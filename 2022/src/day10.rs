@@ -154,6 +154,13 @@ fn part2(file_contents: String) -> String {
 #[cfg(test)]
 mod tests {
     use run_aoc::test_fn;
+    use utils::ocr::ocr_str;
+
+    #[test]
+    fn test_ocr_input() {
+        let input = std::fs::read_to_string("inputs/day10-input.txt").unwrap();
+        assert_eq!(ocr_str(&super::part2(input)), Ok(String::from("PCPBKAPJ")));
+    }
 
     test_fn!(day10, part1, example, 13140);
     test_fn!(day10, part1, input, 14320);
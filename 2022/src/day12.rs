@@ -221,11 +221,134 @@ fn part2(file_contents: String) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use run_aoc::prop::{check_against_reference, shrink_grid, Generator};
     use run_aoc::test_fn;
+    use utils::rng::Rng;
 
     test_fn!(day12, part1, example, 31);
     test_fn!(day12, part1, input, 361);
 
     test_fn!(day12, part2, example, 29);
     test_fn!(day12, part2, input, 354);
+
+    const GENERATOR: Generator = Generator {
+        generate: gen_input,
+        shrink: shrink_grid,
+    };
+
+    // small height maps that always have a path from S to E
+    // (the heights go up along a zig-zag through the grid, with random bumps that keep it climbable)
+    fn gen_input(rng: &mut Rng) -> String {
+        let num_rows = rng.range(3, 6);
+        let num_cols = rng.range(26_usize.div_ceil(num_rows), 9);
+        let num_cells = num_rows * num_cols;
+        let path: Vec<usize> = (0..num_cells).map(|i| i * 25 / (num_cells - 1)).collect();
+        let mut heights = path.clone();
+        for i in 1..(num_cells - 1) {
+            if rng.chance(30) {
+                // still reachable from the previous cell, and can still reach the next one
+                let min = path[i + 1].saturating_sub(1);
+                let max = (heights[i - 1] + 1).min(25);
+                heights[i] = rng.range(min, max);
+            }
+        }
+
+        let mut grid = vec![vec!['a'; num_cols]; num_rows];
+        for (i, height) in heights.iter().enumerate() {
+            let row = i / num_cols;
+            let col = if row % 2 == 0 {
+                i % num_cols
+            } else {
+                num_cols - 1 - i % num_cols
+            };
+            grid[row][col] = match i {
+                0 => 'S',
+                _ if i == num_cells - 1 => 'E',
+                _ => char::from(b'a' + *height as u8),
+            };
+        }
+        grid.iter()
+            .map(|row| format!("{}\n", row.iter().collect::<String>()))
+            .collect()
+    }
+
+    // relax the distance to every cell until nothing changes (slow, but simple)
+    fn brute_force_steps(input: &str, from_any_a: bool) -> Option<usize> {
+        let grid: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+        let height = |c: char| match c {
+            'S' => 0,
+            'E' => 25,
+            _ => c as usize - 'a' as usize,
+        };
+        let find = |target: char| {
+            grid.iter()
+                .enumerate()
+                .find_map(|(r, row)| row.iter().position(|c| *c == target).map(|c| (r, c)))
+                .expect("missing start or end")
+        };
+        let _start = find('S');
+        let end = find('E');
+
+        let mut dist: Vec<Vec<Option<usize>>> = grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|c| match (c, from_any_a) {
+                        ('S', _) => Some(0),
+                        (_, true) if height(*c) == 0 => Some(0),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for r in 0..grid.len() {
+                for c in 0..grid[r].len() {
+                    let d = match dist[r][c] {
+                        Some(d) => d,
+                        None => continue,
+                    };
+                    let neighbors = [
+                        (r.wrapping_sub(1), c),
+                        (r + 1, c),
+                        (r, c.wrapping_sub(1)),
+                        (r, c + 1),
+                    ];
+                    for (nr, nc) in neighbors {
+                        if nr >= grid.len() || nc >= grid[nr].len() {
+                            continue;
+                        }
+                        if height(grid[nr][nc]) > height(grid[r][c]) + 1 {
+                            continue;
+                        }
+                        if !matches!(dist[nr][nc], Some(nd) if nd <= d + 1) {
+                            dist[nr][nc] = Some(d + 1);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+        dist[end.0][end.1]
+    }
+
+    fn brute_force_part1(input: String) -> usize {
+        brute_force_steps(&input, false).expect("no path")
+    }
+
+    fn brute_force_part2(input: String) -> usize {
+        brute_force_steps(&input, true).expect("no path")
+    }
+
+    #[test]
+    fn part1_vs_brute_force() {
+        check_against_reference(12, &GENERATOR, super::part1, brute_force_part1);
+    }
+
+    #[test]
+    fn part2_vs_brute_force() {
+        check_against_reference(12, &GENERATOR, super::part2, brute_force_part2);
+    }
 }
@@ -189,11 +189,147 @@ fn part2(file_contents: String) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
+
+    use run_aoc::prop::{check_against_reference, shrink_groups, Generator};
     use run_aoc::test_fn;
+    use utils::rng::Rng;
 
     test_fn!(day13, part1, example, 13);
     test_fn!(day13, part1, input, 6235);
 
     test_fn!(day13, part2, example, 140);
     test_fn!(day13, part2, input, 22866);
+
+    const GENERATOR: Generator = Generator {
+        generate: gen_input,
+        shrink: shrink_groups,
+    };
+
+    // a few pairs of small nested packets
+    fn gen_input(rng: &mut Rng) -> String {
+        let num_pairs = rng.range(1, 6);
+        let pairs: Vec<String> = (0..num_pairs)
+            .map(|_| format!("{}\n{}", gen_packet(rng), gen_packet(rng)))
+            .collect();
+        format!("{}\n", pairs.join("\n\n"))
+    }
+
+    fn gen_packet(rng: &mut Rng) -> String {
+        loop {
+            let packet = gen_list(rng, 0);
+            // packets that are equal to a divider packet have no well-defined position
+            let stripped = packet.replace(['[', ']'], "");
+            if stripped != "2" && stripped != "6" {
+                return packet;
+            }
+        }
+    }
+
+    fn gen_list(rng: &mut Rng, depth: usize) -> String {
+        let items: Vec<String> = (0..rng.range(0, 4))
+            .map(|_| {
+                if depth < 3 && rng.chance(30) {
+                    gen_list(rng, depth + 1)
+                } else {
+                    rng.range(0, 10).to_string()
+                }
+            })
+            .collect();
+        format!("[{}]", items.join(","))
+    }
+
+    #[derive(Clone)]
+    enum Value {
+        Int(usize),
+        List(Vec<Value>),
+    }
+
+    // simple recursive descent, instead of nom
+    fn parse_value(chars: &[char], pos: &mut usize) -> Value {
+        if chars[*pos] == '[' {
+            *pos += 1;
+            let mut items = vec![];
+            while chars[*pos] != ']' {
+                items.push(parse_value(chars, pos));
+                if chars[*pos] == ',' {
+                    *pos += 1;
+                }
+            }
+            *pos += 1;
+            Value::List(items)
+        } else {
+            let start = *pos;
+            while chars[*pos].is_ascii_digit() {
+                *pos += 1;
+            }
+            let num: String = chars[start..*pos].iter().collect();
+            Value::Int(num.parse().expect("not a number"))
+        }
+    }
+
+    fn parse_packet(line: &str) -> Value {
+        let chars: Vec<char> = line.chars().collect();
+        let mut pos = 0;
+        let value = parse_value(&chars, &mut pos);
+        assert_eq!(pos, chars.len());
+        value
+    }
+
+    fn compare(left: &Value, right: &Value) -> Ordering {
+        match (left, right) {
+            (Value::Int(l), Value::Int(r)) => l.cmp(r),
+            (Value::List(l), Value::List(r)) => l
+                .iter()
+                .zip(r.iter())
+                .map(|(a, b)| compare(a, b))
+                .find(|o| *o != Ordering::Equal)
+                .unwrap_or(l.len().cmp(&r.len())),
+            (Value::Int(_), Value::List(_)) => compare(&Value::List(vec![left.clone()]), right),
+            (Value::List(_), Value::Int(_)) => compare(left, &Value::List(vec![right.clone()])),
+        }
+    }
+
+    fn parse_all(input: &str) -> Vec<Value> {
+        assert!(!input.trim().is_empty());
+        input
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(parse_packet)
+            .collect()
+    }
+
+    fn brute_force_part1(input: String) -> usize {
+        let packets = parse_all(&input);
+        packets
+            .chunks(2)
+            .enumerate()
+            .filter(|(_, pair)| compare(&pair[0], &pair[1]) == Ordering::Less)
+            .map(|(i, _)| i + 1)
+            .sum()
+    }
+
+    // no sorting, just count how many packets come before each divider
+    fn brute_force_part2(input: String) -> usize {
+        let packets = parse_all(&input);
+        let divider2 = parse_packet("[[2]]");
+        let divider6 = parse_packet("[[6]]");
+        let before = |divider: &Value| {
+            packets
+                .iter()
+                .filter(|p| compare(p, divider) == Ordering::Less)
+                .count()
+        };
+        (before(&divider2) + 1) * (before(&divider6) + 2)
+    }
+
+    #[test]
+    fn part1_vs_brute_force() {
+        check_against_reference(13, &GENERATOR, super::part1, brute_force_part1);
+    }
+
+    #[test]
+    fn part2_vs_brute_force() {
+        check_against_reference(13, &GENERATOR, super::part2, brute_force_part2);
+    }
 }
@@ -271,11 +271,103 @@ fn part2(file_contents: String) -> i32 {
 
 #[cfg(test)]
 mod tests {
+    use run_aoc::prop::{check_against_reference, shrink_grid, Generator};
     use run_aoc::test_fn;
+    use utils::rng::Rng;
 
     test_fn!(day8, part1, example, 21);
     test_fn!(day8, part1, input, 1835);
 
     test_fn!(day8, part2, example, 8);
     test_fn!(day8, part2, input, 263670);
+
+    const GENERATOR: Generator = Generator {
+        generate: gen_input,
+        shrink: shrink_grid,
+    };
+
+    // small grids of tree heights
+    fn gen_input(rng: &mut Rng) -> String {
+        let num_rows = rng.range(1, 6);
+        let num_cols = rng.range(1, 6);
+        (0..num_rows)
+            .map(|_| {
+                let row: String = (0..num_cols)
+                    .map(|_| char::from(b'0' + rng.range(0, 9) as u8))
+                    .collect();
+                format!("{}\n", row)
+            })
+            .collect()
+    }
+
+    fn parse_grid(input: &str) -> Vec<Vec<u32>> {
+        let grid: Vec<Vec<u32>> = input
+            .lines()
+            .map(|l| {
+                l.chars()
+                    .map(|c| c.to_digit(10).expect("not a digit"))
+                    .collect()
+            })
+            .collect();
+        assert!(!grid.is_empty() && grid.iter().all(|row| !row.is_empty()));
+        grid
+    }
+
+    // the trees in each direction from the input tree, nearest first
+    fn lines_of_sight(grid: &[Vec<u32>], row: usize, col: usize) -> Vec<Vec<u32>> {
+        let num_rows = grid.len();
+        let num_cols = grid[0].len();
+        vec![
+            (0..col).rev().map(|c| grid[row][c]).collect(),
+            (0..row).rev().map(|r| grid[r][col]).collect(),
+            ((col + 1)..num_cols).map(|c| grid[row][c]).collect(),
+            ((row + 1)..num_rows).map(|r| grid[r][col]).collect(),
+        ]
+    }
+
+    fn brute_force_part1(input: String) -> usize {
+        let grid = parse_grid(&input);
+        let mut num_visible = 0;
+        for row in 0..grid.len() {
+            for col in 0..grid[0].len() {
+                let height = grid[row][col];
+                if lines_of_sight(&grid, row, col)
+                    .iter()
+                    .any(|line| line.iter().all(|h| *h < height))
+                {
+                    num_visible += 1;
+                }
+            }
+        }
+        num_visible
+    }
+
+    fn brute_force_part2(input: String) -> i32 {
+        let grid = parse_grid(&input);
+        let mut best = 0;
+        for row in 0..grid.len() {
+            for col in 0..grid[0].len() {
+                let height = grid[row][col];
+                let score: usize = lines_of_sight(&grid, row, col)
+                    .iter()
+                    .map(|line| match line.iter().position(|h| *h >= height) {
+                        Some(i) => i + 1,
+                        None => line.len(),
+                    })
+                    .product();
+                best = best.max(score as i32);
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn part1_vs_brute_force() {
+        check_against_reference(8, &GENERATOR, super::part1, brute_force_part1);
+    }
+
+    #[test]
+    fn part2_vs_brute_force() {
+        check_against_reference(8, &GENERATOR, super::part2, brute_force_part2);
+    }
 }
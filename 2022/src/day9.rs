@@ -265,7 +265,11 @@ fn part2(file_contents: String) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
+    use run_aoc::prop::{check_against_reference, shrink_lines, Generator};
     use run_aoc::test_fn;
+    use utils::rng::Rng;
 
     test_fn!(day9, part1, example, 13);
     test_fn!(day9, part1, input, 5683);
@@ -274,4 +278,72 @@ mod tests {
     test_fn!(day9, part2, example2, 36);
 
     test_fn!(day9, part2, input, 2372);
+
+    const GENERATOR: Generator = Generator {
+        generate: gen_input,
+        shrink: shrink_lines,
+    };
+
+    // short lists of rope moves
+    fn gen_input(rng: &mut Rng) -> String {
+        let num_moves = rng.range(1, 20);
+        (0..num_moves)
+            .map(|_| {
+                format!(
+                    "{} {}\n",
+                    rng.choose(&['L', 'U', 'R', 'D']),
+                    rng.range(1, 6)
+                )
+            })
+            .collect()
+    }
+
+    // every knot just moves one step toward the one in front of it
+    fn brute_force_tail_positions(input: &str, num_knots: usize) -> usize {
+        assert!(!input.is_empty());
+        let mut knots = vec![(0i32, 0i32); num_knots];
+        let mut visited = HashSet::new();
+        for line in input.lines() {
+            let (dir, dist) = line.split_once(' ').expect("no space");
+            let (dx, dy) = match dir {
+                "L" => (-1, 0),
+                "U" => (0, 1),
+                "R" => (1, 0),
+                "D" => (0, -1),
+                _ => panic!("unknown direction {}", dir),
+            };
+            for _ in 0..dist.parse::<usize>().expect("not a number") {
+                knots[0].0 += dx;
+                knots[0].1 += dy;
+                for k in 1..num_knots {
+                    let delta_x = knots[k - 1].0 - knots[k].0;
+                    let delta_y = knots[k - 1].1 - knots[k].1;
+                    if delta_x.abs() > 1 || delta_y.abs() > 1 {
+                        knots[k].0 += delta_x.signum();
+                        knots[k].1 += delta_y.signum();
+                    }
+                }
+                visited.insert(knots[num_knots - 1]);
+            }
+        }
+        visited.len()
+    }
+
+    fn brute_force_part1(input: String) -> usize {
+        brute_force_tail_positions(&input, 2)
+    }
+
+    fn brute_force_part2(input: String) -> usize {
+        brute_force_tail_positions(&input, 10)
+    }
+
+    #[test]
+    fn part1_vs_brute_force() {
+        check_against_reference(9, &GENERATOR, super::part1, brute_force_part1);
+    }
+
+    #[test]
+    fn part2_vs_brute_force() {
+        check_against_reference(9, &GENERATOR, super::part2, brute_force_part2);
+    }
 }
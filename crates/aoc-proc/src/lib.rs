@@ -6,8 +6,23 @@ use quote::quote;
 use syn::{parse_macro_input, ItemFn};
 
 // create a runner function to wrap the input function and Display its output
+// (line endings are normalized before the input function is called,
+//  unless the attribute is #[runner_fn(raw_input)], and test_fn! does the same)
 #[proc_macro_attribute]
-pub fn runner_fn(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn runner_fn(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+    let normalize = match attr.to_string().as_str() {
+        "" => quote!(run_aoc::input::normalize_line_endings(file_contents)),
+        "raw_input" => quote!(file_contents),
+        other => {
+            return syn::Error::new(
+                Span::call_site(),
+                format!("unknown runner_fn option '{}', expected 'raw_input'", other),
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
     let original_fn = parse_macro_input!(input as ItemFn);
     // this contains the function name, and params
     let signature = original_fn.sig.clone();
@@ -16,8 +31,11 @@ pub fn runner_fn(_attr: TokenStream, input: TokenStream) -> TokenStream {
     let ident = signature.ident;
     let ident_name = format!("{}", ident);
 
-    let runner_name = if ident_name == "part1" || ident_name == "part2" {
-        Ident::new(&format!("__{}_runner", ident_name), Span::call_site())
+    let (runner_name, input_name) = if ident_name == "part1" || ident_name == "part2" {
+        (
+            Ident::new(&format!("__{}_runner", ident_name), Span::call_site()),
+            Ident::new(&format!("__{}_input", ident_name), Span::call_site()),
+        )
     } else {
         return syn::Error::new(
             ident.span(),
@@ -27,14 +45,24 @@ pub fn runner_fn(_attr: TokenStream, input: TokenStream) -> TokenStream {
         .into();
     };
 
+    // the input the way the runner gets it (the tests use this too)
+    let input_fn = quote!(
+        pub fn #input_name(file_contents: String) -> String {
+            #normalize
+        }
+    );
+
     let num_args = signature.inputs.len();
 
     match num_args {
         1 => TokenStream::from(quote!(
             #original_fn
 
+            #input_fn
+
             // only takes one arg, doesn't expect params
             pub fn #runner_name(file_contents: String, _p: Option<run_aoc::cli::Params>) -> String {
+                let file_contents = #input_name(file_contents);
                 let result = #ident(file_contents);
                 format!("{}", result)
             }
@@ -42,7 +70,10 @@ pub fn runner_fn(_attr: TokenStream, input: TokenStream) -> TokenStream {
         2 => TokenStream::from(quote!(
             #original_fn
 
+            #input_fn
+
             pub fn #runner_name(file_contents: String, p: Option<run_aoc::cli::Params>) -> String {
+                let file_contents = #input_name(file_contents);
                 let result = #ident(file_contents, p);
                 format!("{}", result)
             }
@@ -72,7 +103,67 @@ pub fn test_fn(input: TokenStream) -> TokenStream {
         })
         .collect();
 
-    if tt.len() == 4 {
+    if tt.len() == 3 {
+        // no expected answer, it's in answers.txt
+        // ex: aoc_test!(day1, part1, input);
+        match (&tt[0], &tt[1], &tt[2]) {
+            (TokenTree::Ident(day), TokenTree::Ident(part_ident), TokenTree::Ident(variation)) => {
+                // hacky way to specify a slow test
+                let fn_name = part_ident.to_string();
+                let (part, maybe_ignore) = if fn_name.contains("_SLOW") {
+                    let replaced_part = fn_name.replace("_SLOW", "");
+                    (replaced_part, quote!(#[ignore]))
+                } else {
+                    (fn_name, quote!())
+                };
+                let (day_num, part_enum) = match (
+                    day.to_string().trim_start_matches("day").parse::<usize>(),
+                    part.as_str(),
+                ) {
+                    (Ok(d), "part1") => (d, quote!(run_aoc::cli::Part::One)),
+                    (Ok(d), "part2") => (d, quote!(run_aoc::cli::Part::Two)),
+                    _ => {
+                        return syn::Error::new(
+                            Span::call_site(),
+                            format!("expected dayN and part1/part2, found {} and {}", day, part),
+                        )
+                        .to_compile_error()
+                        .into()
+                    }
+                };
+
+                let test_name = Ident::new(&format!("{}_{}", part, variation), Span::call_site());
+                let file = format!("inputs/{}-{}.txt", day, variation);
+                let file_name = Literal::string(&file);
+                let fail_literal = Literal::string(&format!("failed to read file '{}'", file));
+                let input_fn = Ident::new(&format!("__{}_input", part), Span::call_site());
+                let part_fn = Ident::new(&part, Span::call_site());
+                let variation_name = Literal::string(&variation.to_string());
+                TokenStream::from(quote!(
+                    #[test]
+                    #maybe_ignore
+                    fn #test_name() {
+                        let file = #file_name;
+                        let expected = run_aoc::answers::expected(#day_num, #part_enum, #variation_name)
+                            .unwrap_or_else(|e| panic!("{}", e));
+                        let input = run_aoc::input::read_input(&file).expect(#fail_literal);
+                        let input = super::#input_fn(input);
+                        let output = super::#part_fn(input).to_string();
+                        assert_eq!(run_aoc::answers::as_submitted(&output), expected);
+                    }
+                ))
+            }
+            _ => syn::Error::new(
+                Span::call_site(),
+                format!(
+                    "expected args of type (ident, ident, ident), found: {:?}",
+                    tt
+                ),
+            )
+            .to_compile_error()
+            .into(),
+        }
+    } else if tt.len() == 4 {
         // no params
         // ex: aoc_test!(day1, part1, example, 24000);
         match (&tt[0], &tt[1], &tt[2], &tt[3]) {
@@ -95,13 +186,15 @@ pub fn test_fn(input: TokenStream) -> TokenStream {
                 let file = format!("inputs/{}-{}.txt", day, variation);
                 let file_name = Literal::string(&file);
                 let fail_literal = Literal::string(&format!("failed to read file '{}'", file));
+                let input_fn = Ident::new(&format!("__{}_input", part), Span::call_site());
                 let part_fn = Ident::new(&format!("{}", part), Span::call_site());
                 TokenStream::from(quote!(
                     #[test]
                     #maybe_ignore
                     fn #test_name() {
                         let file = #file_name;
-                        let input = std::fs::read_to_string(&file).expect(#fail_literal);
+                        let input = run_aoc::input::read_input(&file).expect(#fail_literal);
+                        let input = super::#input_fn(input);
                         assert_eq!(super::#part_fn(input), #expected);
                     }
                 ))
@@ -139,6 +232,7 @@ pub fn test_fn(input: TokenStream) -> TokenStream {
                 let file = format!("inputs/{}-{}.txt", day, variation);
                 let file_name = Literal::string(&file);
                 let fail_literal = Literal::string(&format!("failed to read file '{}'", file));
+                let input_fn = Ident::new(&format!("__{}_input", part), Span::call_site());
                 let part_fn = Ident::new(&format!("{}", part), Span::call_site());
 
                 TokenStream::from(quote!(
@@ -147,7 +241,8 @@ pub fn test_fn(input: TokenStream) -> TokenStream {
                     fn #test_name() {
                         let file = #file_name;
                         let params = #params.parse().expect("could not parse params");
-                        let input = std::fs::read_to_string(&file).expect(#fail_literal);
+                        let input = run_aoc::input::read_input(&file).expect(#fail_literal);
+                        let input = super::#input_fn(input);
                         assert_eq!(super::#part_fn(input, Some(params)), #expected);
                     }
                 ))
@@ -165,7 +260,7 @@ pub fn test_fn(input: TokenStream) -> TokenStream {
     } else {
         syn::Error::new(
             Span::call_site(),
-            format!("this macro takes 4 or 5 arguments, found {}", tt.len()),
+            format!("this macro takes 3, 4 or 5 arguments, found {}", tt.len()),
         )
         .to_compile_error()
         .into()
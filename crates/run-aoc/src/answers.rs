@@ -0,0 +1,171 @@
+use std::fs;
+use std::str::FromStr;
+
+use crate::cli::Part;
+use crate::parse::{parse_description, ParseMode};
+use crate::puzzle::Puzzle;
+
+// the correct answers for each input, one file per year (next to descriptions/ and inputs/)
+// (used by 'test_fn!' when it isn't given the expected answer)
+const ANSWERS_FILE: &str = "answers.txt";
+
+// the answers on the description pages are for the real input
+const INPUT_VARIATION: &str = "input";
+
+// one line per answer, tab-separated
+#[derive(Debug, Eq, PartialEq)]
+pub struct KnownAnswer {
+    pub day: usize,
+    pub part: Part,
+    // like 'input' or 'example' (same as the input file names)
+    pub variation: String,
+    pub answer: String,
+}
+
+impl KnownAnswer {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\n",
+            self.day, self.part, self.variation, self.answer
+        )
+    }
+}
+
+impl FromStr for KnownAnswer {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 4 {
+            return Err(format!(
+                "expected 4 fields in answer line, found {}",
+                fields.len()
+            ));
+        }
+        Ok(KnownAnswer {
+            day: fields[0]
+                .parse()
+                .or(Err(format!("could not parse '{}' as a number", fields[0])))?,
+            part: fields[1].parse()?,
+            variation: fields[2].to_string(),
+            answer: fields[3].to_string(),
+        })
+    }
+}
+
+pub fn read() -> Result<Vec<KnownAnswer>, String> {
+    let contents = match fs::read_to_string(ANSWERS_FILE) {
+        Ok(s) => s,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => {
+            return Err(format!("Failed to read {ANSWERS_FILE}: {err}"));
+        }
+    };
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.parse()
+                .map_err(|e| format!("{ANSWERS_FILE} line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+// (sorted, so the file is easy to read and diff)
+fn write(answers: &mut [KnownAnswer]) -> Result<(), String> {
+    answers.sort_by(|a, b| (a.day, a.part, &a.variation).cmp(&(b.day, b.part, &b.variation)));
+    let contents: String = answers.iter().map(|a| a.to_line()).collect();
+    fs::write(ANSWERS_FILE, contents).map_err(|e| format!("Failed to write {ANSWERS_FILE}: {e}"))
+}
+
+// the answer that a test expects
+pub fn expected(day: usize, part: Part, variation: &str) -> Result<String, String> {
+    read()?
+        .into_iter()
+        .find(|a| a.day == day && a.part == part && a.variation == variation)
+        .map(|a| a.answer)
+        .ok_or(format!(
+            "no known answer for day {day} part {part} '{variation}' in {ANSWERS_FILE} (try 'answers import {day}')"
+        ))
+}
+
+// the solution output, the way it would be submitted (and shown in the description)
+// (multi-line output is block letters, like 2022 day 10 part 2, so that's the letters)
+pub fn as_submitted(output: &str) -> String {
+    crate::submit::answer_to_submit(output).unwrap_or(output.to_string())
+}
+
+// add or replace an answer, and return what it was before
+fn set(answers: &mut Vec<KnownAnswer>, day: usize, part: Part, answer: &str) -> Option<String> {
+    match answers
+        .iter_mut()
+        .find(|a| a.day == day && a.part == part && a.variation == INPUT_VARIATION)
+    {
+        Some(existing) => Some(std::mem::replace(&mut existing.answer, answer.to_string())),
+        None => {
+            answers.push(KnownAnswer {
+                day,
+                part,
+                variation: String::from(INPUT_VARIATION),
+                answer: answer.to_string(),
+            });
+            None
+        }
+    }
+}
+
+// save the answers from the downloaded descriptions ("Your puzzle answer was ...")
+// (the description is downloaded again after a correct submission, so it has the answers)
+pub fn import(year: usize, day: Option<usize>) -> Result<(), String> {
+    let all_days = day.is_none();
+    let days: Vec<usize> = match day {
+        Some(d) => vec![d],
+        None => (1..=25).collect(),
+    };
+    let mut answers = read()?;
+    let mut num_imported = 0;
+    for day in days {
+        let puzzle = Puzzle::new(year, day);
+        let description = match fs::read_to_string(puzzle.html_path()) {
+            Ok(html) => parse_description(&html, &puzzle, ParseMode::Lenient)
+                .map(|(description, _)| description)
+                .map_err(|e| format!("day {day}: {e}")),
+            // (for all days, only the ones that have been downloaded)
+            Err(err) if all_days && err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => Err(format!(
+                "Failed to read '{}': {err} (download it with 'html {day}')",
+                puzzle.html_path()
+            )),
+        };
+        // (for all days, one bad description doesn't lose the answers from the others)
+        let description = match description {
+            Ok(d) => d,
+            Err(err) if all_days => {
+                println!("warning: {err}");
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        for part in [Part::One, Part::Two] {
+            let answer = match description.answer(part) {
+                Some(a) => a,
+                None => continue,
+            };
+            match set(&mut answers, day, part, answer) {
+                Some(before) if before == answer => (),
+                Some(before) => {
+                    println!("day {day} part {part}: {answer} (was {before})");
+                    num_imported += 1;
+                }
+                None => {
+                    println!("day {day} part {part}: {answer}");
+                    num_imported += 1;
+                }
+            }
+        }
+    }
+    write(&mut answers)?;
+    println!("imported {num_imported} answer(s) into {ANSWERS_FILE}");
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use nom::bytes::complete::tag;
+use nom::bytes::complete::take_until;
+use nom::sequence::preceded;
+use nom::IResult;
+
+use crate::download::{agent_for_dl, base_url, session_cookie, url_to_buf, CookieSource};
+
+// make sure the session cookie works, by fetching a page that only logged-in users get
+// (the settings page redirects to the login page otherwise)
+pub fn check() -> Result<(), String> {
+    let (_, source) = session_cookie()?;
+    match &source {
+        CookieSource::EnvVar => println!("session cookie is from AOC_SESSION"),
+        CookieSource::File(path) => {
+            println!("session cookie is from '{}'", path.display());
+            if let Some(warning) = permissions_warning(path) {
+                println!("warning: {warning}");
+            }
+        }
+    }
+
+    let url = format!("{}/settings", base_url());
+    println!("checking session --> {url}");
+    let agent = agent_for_dl()?;
+    let bytes = url_to_buf(&url, &agent)?;
+    let html = String::from_utf8_lossy(&bytes);
+    match account_name(&html) {
+        Ok((_, name)) => {
+            println!("logged in as '{}'", name.trim());
+            Ok(())
+        }
+        Err(_) => Err(String::from(
+            "not logged in - the session cookie is expired or invalid",
+        )),
+    }
+}
+
+// the page header shows the user, like
+// <div class="user">name <span class="star-count">50*</span></div>
+fn account_name(html: &str) -> IResult<&str, &str> {
+    preceded(
+        preceded(
+            take_until("<div class=\"user\">"),
+            tag("<div class=\"user\">"),
+        ),
+        take_until("<"),
+    )(html)
+}
+
+// the session cookie is as good as a password, so other users shouldn't be able to read it
+#[cfg(unix)]
+fn permissions_warning(path: &Path) -> Option<String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path).ok()?.permissions().mode();
+    if mode & 0o004 != 0 {
+        Some(format!(
+            "'{}' is world-readable (mode {:o}), fix with 'chmod 600 {}'",
+            path.display(),
+            mode & 0o777,
+            path.display()
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn permissions_warning(_path: &Path) -> Option<String> {
+    None
+}
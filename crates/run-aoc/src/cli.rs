@@ -1,7 +1,9 @@
 use std::collections::HashMap;
-use std::{fmt, fs, str::FromStr};
+use std::time::{Duration, Instant};
+use std::{fmt, str::FromStr};
 
 use crate::download::DLOpt;
+use crate::leaderboard::Format;
 
 pub fn usage() {
     println!(
@@ -21,6 +23,72 @@ Usage:
     Options:
         --force,-f  overwrite the file it if already exists
 
+  DOWNLOAD input for a specific day:
+    cargo run -- input <1-25> [options]
+
+    Options:
+        --force,-f  overwrite the file it if already exists
+
+  INPUTS encryption, so they can be committed to a public repo:
+    cargo run -- inputs encrypt
+    cargo run -- inputs decrypt
+
+    (encrypted inputs are used when the plaintext file doesn't exist)
+
+  FETCH the descriptions and inputs for every unlocked day:
+    cargo run -- fetch-all [options]
+
+    Options:
+        --from <N>  first day to fetch (default: 1)
+        --to <M>    last day to fetch (default: 25)
+
+    (files that already exist are skipped, so it can be run again to resume)
+
+  WAIT for a day to unlock, then download the description and input:
+    cargo run -- wait <1-25>
+
+  SUBMIT an answer for a specific day:
+    cargo run -- submit <1-25> <one|two> [answer]
+
+    Optional:
+        answer      the answer to submit (default: run the solution with the 'input' file)
+
+  HISTORY of recorded runs for a specific day:
+    cargo run -- history <1-25> <one|two> [input-type] [options]
+
+    Optional:
+        input-type  which input the runs used (default: 'input')
+
+    Options:
+        --threshold,-t <N>  flag runs more than N% slower than the best (default: 10)
+
+  STATUS of the stars for this year, and the local files for each day:
+    cargo run -- status [options]
+
+    Options:
+        --all       also show the stars for all years
+
+  LEADERBOARD for a private leaderboard:
+    cargo run -- leaderboard <id> [options]
+
+    Options:
+        --format <table|json>  show a table, or the JSON from the server (default: table)
+        --day <1-25>           show when each member completed that day
+
+    (the leaderboard is cached for 15 minutes)
+
+  ANSWERS from the description pages, saved as the known answers for the 'input' files:
+    cargo run -- answers import [1-25|all]
+
+    (default: all days with a downloaded description)
+    (tests like 'test_fn!(day1, part1, input)' use the known answers)
+
+  AUTH check that the session cookie works:
+    cargo run -- auth check
+
+    (the cookie is read from AOC_SESSION, or from ~/.aoc-session-cookie)
+    (requests also need contact info for the User-Agent, from AOC_USER_AGENT or ~/.aoc-user-agent)
+
   HELP
     cargo run -- help
     cargo run -- --help
@@ -29,6 +97,7 @@ Usage:
     );
 }
 
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum Part {
     One,
     Two,
@@ -117,32 +186,40 @@ fn args_for_day(
         }
         None => None,
     };
-    let file_path = format!("inputs/day{}-{}.txt", day, input);
+    let file_path = input_file_path(day, input);
     Ok((day, part, params, file_path))
 }
 
+pub fn input_file_path(day: usize, input: &str) -> String {
+    format!("inputs/day{}-{}.txt", day, input)
+}
+
+// returns the answer and how long the day function took to run
 pub fn run_day_fn(
     day_fn: fn(String, Option<Params>) -> String,
     params: Option<Params>,
     file_path: String,
-) -> Result<(), String> {
+) -> Result<(String, Duration), String> {
     println!("Params: {:?}", params);
     println!("reading file '{}'", file_path);
-    let file_contents = match fs::read_to_string(file_path) {
-        Ok(s) => s,
-        Err(err) => {
-            let err_str = if let Some(inner_err) = err.into_inner() {
-                format!("{inner_err}")
-            } else {
-                format!("Some std::io::Error happened")
-            };
-            return Err(format!("Failed to read file: {err_str}"));
-        }
-    };
+    let file_contents = crate::input::read_input(&file_path)?;
 
+    for warning in crate::input::check(&file_contents) {
+        println!("warning: {}", warning);
+    }
+
+    let start = Instant::now();
     let answer = day_fn(file_contents, params);
+    let duration = start.elapsed();
     println!("\nanswer:\n{}", answer);
-    Ok(())
+    // block letter output (like a CRT display) is easier to read as text
+    if answer.contains('\n') {
+        if let Ok(letters) = utils::ocr::ocr_str(&answer) {
+            println!("\nletters:\n{}", letters);
+        }
+    }
+    println!("\n(took {:.3?})", duration);
+    Ok((answer, duration))
 }
 
 // TODO for now just return the day
@@ -160,6 +237,18 @@ pub fn parse_dl_args(args: &[String]) -> Result<usize, String> {
 }
 
 pub fn parse_html_args(args: &[String]) -> Result<(usize, DLOpt), String> {
+    parse_day_and_dl_opt("html", args)
+}
+
+pub fn parse_md_args(args: &[String]) -> Result<(usize, DLOpt), String> {
+    parse_day_and_dl_opt("md", args)
+}
+
+pub fn parse_input_args(args: &[String]) -> Result<(usize, DLOpt), String> {
+    parse_day_and_dl_opt("input", args)
+}
+
+fn parse_day_and_dl_opt(cmd: &str, args: &[String]) -> Result<(usize, DLOpt), String> {
     match args.len() {
         1 => match args[0].parse::<usize>() {
             Err(_) => Err(format!("could not parse day '{}' as a number", args[0])),
@@ -177,32 +266,179 @@ pub fn parse_html_args(args: &[String]) -> Result<(usize, DLOpt), String> {
         }
 
         _ => Err(format!(
-            "expected 1 or 2 args to 'html', found {}",
+            "expected 1 or 2 args to '{}', found {}",
+            cmd,
             args.len()
         )),
     }
 }
 
-pub fn parse_md_args(args: &[String]) -> Result<(usize, DLOpt), String> {
+pub fn parse_history_args(args: &[String]) -> Result<(usize, Part, String, f64), String> {
+    if args.len() < 2 {
+        return Err(format!(
+            "expected at least 2 args to 'history', found {}",
+            args.len()
+        ));
+    }
+    let day = args[0].parse::<usize>().or(Err(format!(
+        "could not parse day '{}' as a number",
+        args[0]
+    )))?;
+    let part: Part = args[1].parse()?;
+    let mut input = String::from("input");
+    let mut threshold = 10.0;
+    let mut rest = args[2..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--threshold" | "-t" => {
+                let n = rest.next().ok_or(format!("missing value for '{}'", arg))?;
+                threshold = n.parse::<f64>().or(Err(format!(
+                    "could not parse threshold '{}' as a number",
+                    n
+                )))?;
+            }
+            _ => input = arg.to_string(),
+        }
+    }
+    Ok((day, part, input_file_path(day, &input), threshold))
+}
+
+pub fn parse_submit_args(args: &[String]) -> Result<(usize, Part, Option<String>), String> {
     match args.len() {
-        1 => match args[0].parse::<usize>() {
-            Err(_) => Err(format!("could not parse day '{}' as a number", args[0])),
-            Ok(d) => Ok((d, DLOpt::IfNoExist)),
+        2 | 3 => {
+            let day = args[0].parse::<usize>().or(Err(format!(
+                "could not parse day '{}' as a number",
+                args[0]
+            )))?;
+            let part: Part = args[1].parse()?;
+            Ok((day, part, args.get(2).cloned()))
+        }
+        _ => Err(format!(
+            "expected 2 or 3 args to 'submit', found {}",
+            args.len()
+        )),
+    }
+}
+
+// the only sub-command is 'check' for now
+pub fn parse_auth_args(args: &[String]) -> Result<(), String> {
+    match args.len() {
+        1 => match args[0].as_str() {
+            "check" => Ok(()),
+            other => Err(format!("unknown 'auth' sub-command '{}'", other)),
         },
-        2 => {
-            let day = match args[0].parse::<usize>() {
-                Ok(d) => d,
-                Err(_) => {
-                    return Err(format!("could not parse day '{}' as a number", args[0]));
-                }
-            };
-            let opt = args[1].parse()?;
-            Ok((day, opt))
+        _ => Err(format!("expected 1 arg to 'auth', found {}", args.len())),
+    }
+}
+
+pub fn parse_leaderboard_args(args: &[String]) -> Result<(String, Format, Option<usize>), String> {
+    if args.is_empty() {
+        return Err(String::from(
+            "expected at least 1 arg to 'leaderboard', found 0",
+        ));
+    }
+    let id = args[0].to_string();
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("leaderboard id should be a number, found '{}'", id));
+    }
+    let mut format = Format::Table;
+    let mut day = None;
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        let value = rest.next().ok_or(format!("missing value for '{}'", arg))?;
+        match arg.as_str() {
+            "--format" => format = value.parse()?,
+            "--day" => {
+                day = Some(
+                    value
+                        .parse::<usize>()
+                        .or(Err(format!("could not parse day '{}' as a number", value)))?,
+                )
+            }
+            _ => {
+                return Err(format!("unknown option '{}' for 'leaderboard'", arg));
+            }
+        }
+    }
+    Ok((id, format, day))
+}
+
+// returns whether to show all years
+pub fn parse_status_args(args: &[String]) -> Result<bool, String> {
+    match args.len() {
+        0 => Ok(false),
+        1 => match args[0].as_str() {
+            "--all" => Ok(true),
+            other => Err(format!("unknown option '{}' for 'status'", other)),
+        },
+        _ => Err(format!(
+            "expected 0 or 1 args to 'status', found {}",
+            args.len()
+        )),
+    }
+}
+
+pub fn parse_wait_args(args: &[String]) -> Result<usize, String> {
+    match args.len() {
+        1 => args[0].parse::<usize>().or(Err(format!(
+            "could not parse day '{}' as a number",
+            args[0]
+        ))),
+        _ => Err(format!("expected 1 arg to 'wait', found {}", args.len())),
+    }
+}
+
+pub fn parse_fetch_all_args(args: &[String]) -> Result<(usize, usize), String> {
+    let mut from = 1;
+    let mut to = 25;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        let value = rest.next().ok_or(format!("missing value for '{}'", arg))?;
+        let day = value
+            .parse::<usize>()
+            .or(Err(format!("could not parse day '{}' as a number", value)))?;
+        match arg.as_str() {
+            "--from" => from = day,
+            "--to" => to = day,
+            _ => {
+                return Err(format!("unknown option '{}' for 'fetch-all'", arg));
+            }
         }
+    }
+    if from < 1 || to > 25 || from > to {
+        return Err(format!(
+            "expected days in 1-25 with --from <= --to, found {}-{}",
+            from, to
+        ));
+    }
+    Ok((from, to))
+}
 
+// returns the day to import, or None for all of them
+pub fn parse_answers_args(args: &[String]) -> Result<Option<usize>, String> {
+    match args.len() {
+        1 | 2 if args[0] != "import" => Err(format!("unknown 'answers' sub-command '{}'", args[0])),
+        1 => Ok(None),
+        2 if args[1] == "all" => Ok(None),
+        2 => match args[1].parse::<usize>() {
+            Ok(d) if (1..=25).contains(&d) => Ok(Some(d)),
+            _ => Err(format!("expected a day 1-25 or 'all', found '{}'", args[1])),
+        },
         _ => Err(format!(
-            "expected 1 or 2 args to 'md', found {}",
+            "expected 1 or 2 args to 'answers', found {}",
             args.len()
         )),
     }
 }
+
+// returns true to encrypt, false to decrypt
+pub fn parse_inputs_args(args: &[String]) -> Result<bool, String> {
+    match args.len() {
+        1 => match args[0].as_str() {
+            "encrypt" => Ok(true),
+            "decrypt" => Ok(false),
+            other => Err(format!("unknown 'inputs' sub-command '{}'", other)),
+        },
+        _ => Err(format!("expected 1 arg to 'inputs', found {}", args.len())),
+    }
+}
@@ -0,0 +1,20 @@
+use std::env;
+use std::fs;
+
+// settings come from an environment variable, or from a file in the home directory
+// (the environment variable wins, so it can be overridden for a single command)
+pub(crate) fn setting(env_var: &str, file_name: &str) -> Option<String> {
+    if let Ok(value) = env::var(env_var) {
+        let value = value.trim().to_string();
+        if !value.is_empty() {
+            return Some(value);
+        }
+    }
+    let file = dirs::home_dir()?.join(file_name);
+    let value = fs::read_to_string(file).ok()?.trim().to_string();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
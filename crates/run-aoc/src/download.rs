@@ -1,6 +1,8 @@
+use std::env;
+use std::fmt;
 use std::fs;
 use std::io::Read;
-use std::path::Path;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 use cookie::time::Duration;
@@ -10,10 +12,22 @@ use ureq;
 use ureq::Agent;
 use url::Url;
 
-// TODO: I use year and day togther so much they should be in a struct
-// (with methods like input_url(), description_url(), etc)
+use crate::input::InputWarning;
 
-#[derive(Eq, PartialEq)]
+pub(crate) mod governor;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+// the server to download from, can be changed with AOC_BASE_URL (or in ~/.aoc-base-url)
+// (mostly so this can be tested against a local server)
+pub(crate) fn base_url() -> String {
+    crate::config::setting("AOC_BASE_URL", ".aoc-base-url")
+        .unwrap_or(String::from(DEFAULT_BASE_URL))
+        .trim_end_matches('/')
+        .to_string()
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum DLOpt {
     Force,
     IfNoExist,
@@ -31,24 +45,151 @@ impl FromStr for DLOpt {
     }
 }
 
-fn url_to_buf(url: &str, agent: &Agent) -> Result<Vec<u8>, String> {
-    let resp = match agent.get(url).call() {
+// reasons a download can't be used (so it's not saved)
+#[derive(Debug, Eq, PartialEq)]
+pub enum BadDownload {
+    LoginRequired,
+    NotUnlocked,
+    NotFound,
+    BadRequest(String),
+    ServerError(u16),
+    Empty,
+    LooksLikeHtml,
+    NoDescription,
+    TooLarge,
+}
+
+impl fmt::Display for BadDownload {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BadDownload::LoginRequired => write!(
+                f,
+                "the server says to log in (the session cookie is missing or expired, try 'auth check')"
+            ),
+            BadDownload::NotUnlocked => write!(f, "the puzzle is not unlocked yet"),
+            BadDownload::NotFound => write!(f, "not found (404)"),
+            BadDownload::BadRequest(body) => write!(f, "bad request (400): {}", body),
+            BadDownload::ServerError(code) => write!(
+                f,
+                "server error ({}), which usually means the session cookie is invalid (try 'auth check')",
+                code
+            ),
+            BadDownload::Empty => write!(f, "the response is empty"),
+            BadDownload::LooksLikeHtml => write!(
+                f,
+                "the response is a web page, not the input (maybe a login or error page?)"
+            ),
+            BadDownload::NoDescription => {
+                write!(f, "the response does not have a puzzle description")
+            }
+            BadDownload::TooLarge => {
+                write!(f, "the response is larger than {} bytes", MAX_RESPONSE_LEN)
+            }
+        }
+    }
+}
+
+// the server doesn't set Content-Length, so cap the read at 10MB
+const MAX_RESPONSE_LEN: u64 = 10_000_000;
+
+pub(crate) fn url_to_buf(url: &str, agent: &Agent) -> Result<Vec<u8>, String> {
+    match conditional_get(url, agent, &Validators::default())? {
+        Some((bytes, _)) => Ok(bytes),
+        None => Err(String::from(
+            "Request failed: 304, but this was not a conditional request",
+        )),
+    }
+}
+
+// what the server said about a response, to check later if it has changed
+// (saved next to the downloaded file)
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    fn file_for(file_loc: &str) -> String {
+        format!("{file_loc}.cache")
+    }
+
+    // (missing or unreadable validators just mean the request is not conditional)
+    pub fn load(file_loc: &str) -> Self {
+        let contents = fs::read_to_string(Validators::file_for(file_loc)).unwrap_or_default();
+        let mut validators = Validators::default();
+        for line in contents.lines() {
+            match line.split_once(": ") {
+                Some(("ETag", v)) => validators.etag = Some(v.to_string()),
+                Some(("Last-Modified", v)) => validators.last_modified = Some(v.to_string()),
+                _ => {}
+            }
+        }
+        validators
+    }
+
+    pub fn save(&self, file_loc: &str) -> Result<(), String> {
+        let cache_file = Validators::file_for(file_loc);
+        if self.etag.is_none() && self.last_modified.is_none() {
+            // (remove any old ones, since they don't apply anymore)
+            let _ = fs::remove_file(cache_file);
+            return Ok(());
+        }
+        let mut contents = String::new();
+        if let Some(etag) = &self.etag {
+            contents.push_str(&format!("ETag: {etag}\n"));
+        }
+        if let Some(last_modified) = &self.last_modified {
+            contents.push_str(&format!("Last-Modified: {last_modified}\n"));
+        }
+        fs::write(cache_file, contents).map_err(|e| format!("Failed to save validators: {e}"))
+    }
+}
+
+// returns None if the server says it's not modified (304)
+// (gzip responses are decoded by ureq)
+pub(crate) fn conditional_get(
+    url: &str,
+    agent: &Agent,
+    validators: &Validators,
+) -> Result<Option<(Vec<u8>, Validators)>, String> {
+    governor::throttle("GET", url)?;
+    let mut req = agent.get(url);
+    if let Some(etag) = &validators.etag {
+        req = req.set("If-None-Match", etag);
+    }
+    if let Some(last_modified) = &validators.last_modified {
+        req = req.set("If-Modified-Since", last_modified);
+    }
+    let resp = match req.call() {
         Ok(r) => r,
         Err(ureq::Error::Status(code, response)) => {
             // unexpected status code (4xx, 5xx, etc)
-            return Err(format!(
-                "Request failed: {code}, {}\nbody:\n{}",
-                String::from(response.status_text()),
-                response.into_string().unwrap_or(String::from("(empty)")),
-            ));
+            let status_text = String::from(response.status_text());
+            let body = response.into_string().unwrap_or(String::from("(empty)"));
+            return Err(match bad_status(code, &body) {
+                Some(bad) => bad.to_string(),
+                None => format!("Request failed: {code}, {status_text}\nbody:\n{body}"),
+            });
         }
         Err(ureq::Error::Transport(t)) => {
             return Err(t.to_string());
         }
     };
-    // the server doesn't set Content-Length, so cap read at 10MB
+    if resp.status() == 304 {
+        return Ok(None);
+    }
+    let new_validators = Validators {
+        etag: resp.header("ETag").map(|s| s.to_string()),
+        last_modified: resp.header("Last-Modified").map(|s| s.to_string()),
+    };
+    // (read one more byte than the max, to know if it was cut off)
     let mut bytes: Vec<u8> = Vec::new();
-    match resp.into_reader().take(10_000_000).read_to_end(&mut bytes) {
+    match resp
+        .into_reader()
+        .take(MAX_RESPONSE_LEN + 1)
+        .read_to_end(&mut bytes)
+    {
         Ok(_) => {}
         Err(err) => {
             let err_str = if let Some(inner_err) = err.into_inner() {
@@ -59,98 +200,76 @@ fn url_to_buf(url: &str, agent: &Agent) -> Result<Vec<u8>, String> {
             return Err(format!("Failed to read URL: {err_str}"));
         }
     }
-    Ok(bytes)
-}
-
-pub fn dl_html(year: usize, day: usize, dlo: DLOpt) -> Result<(), String> {
-    let file_loc_html = format!("descriptions/day{day}.html");
-    // does the HTML file exist?
-    let p = Path::new(&file_loc_html);
-    if let Ok(exists) = p.try_exists() {
-        if exists == true {
-            if dlo == DLOpt::Force {
-                println!("(HTML already exists, but forcing download)");
-            } else {
-                println!("(HTML already exists, skipping download - use --force to overwrite)");
-                return Ok(());
-            }
-        }
+    if bytes.len() as u64 > MAX_RESPONSE_LEN {
+        return Err(BadDownload::TooLarge.to_string());
     }
+    Ok(Some((bytes, new_validators)))
+}
 
-    let url = format!("https://adventofcode.com/{year}/day/{day}");
-    println!("{url} --> {file_loc_html}");
-    let agent = agent_for_dl()?;
-    let bytes = url_to_buf(&url, &agent)?;
-    match fs::write(file_loc_html, bytes) {
-        Ok(_) => Ok(()),
-        Err(err) => {
-            let err_str = if let Some(inner_err) = err.into_inner() {
-                format!("{inner_err}")
-            } else {
-                format!("Some std::io::Error happened")
-            };
-            Err(format!("Failed to write file: {err_str}"))
-        }
+// the known error responses from the server
+fn bad_status(code: u16, body: &str) -> Option<BadDownload> {
+    match code {
+        400 if is_login_message(body) => Some(BadDownload::LoginRequired),
+        400 => Some(BadDownload::BadRequest(body.trim().to_string())),
+        404 if body.contains("before it unlocks") => Some(BadDownload::NotUnlocked),
+        404 => Some(BadDownload::NotFound),
+        500..=599 => Some(BadDownload::ServerError(code)),
+        _ => None,
     }
 }
 
-// first download the HTML file if it doesn't exist, then parse that to markdown
-pub fn dl_md(year: usize, day: usize, dlo: DLOpt) -> Result<(), String> {
-    let file_loc_html = format!("descriptions/day{day}.html");
-    let file_loc_md = format!("descriptions/day{day}.md");
-    // TODO: eventually want to skip writing the HTMl file and go straight to md
-    // (but for testing this is better, to avoid hitting the server every time)
-    dl_html(year, day, dlo)?;
+fn is_login_message(body: &str) -> bool {
+    body.contains("Puzzle inputs differ by user") || body.contains("Please log in")
+}
 
-    let html_contents =
-        fs::read_to_string(&file_loc_html).expect("could not read the file, I know it exists!!!");
-    let md_contents = crate::parse::html_to_md(&html_contents)?;
-    match fs::write(file_loc_md, md_contents) {
-        Ok(_) => {}
-        Err(err) => {
-            let err_str = if let Some(inner_err) = err.into_inner() {
-                format!("{inner_err}")
-            } else {
-                format!("Some std::io::Error happened")
-            };
-            return Err(format!("Failed to write file: {err_str}"));
-        }
+// make sure the input is not an error message or web page
+pub fn check_input(bytes: &[u8]) -> Result<(), BadDownload> {
+    let contents = String::from_utf8_lossy(bytes);
+    if contents.trim().is_empty() {
+        Err(BadDownload::Empty)
+    } else if is_login_message(&contents) {
+        Err(BadDownload::LoginRequired)
+    } else if contents.contains("before it unlocks") {
+        Err(BadDownload::NotUnlocked)
+    } else if crate::input::check(&contents).contains(&InputWarning::LooksLikeHtml) {
+        Err(BadDownload::LooksLikeHtml)
+    } else {
+        Ok(())
     }
-    Ok(())
 }
 
-// input URL example:
-// https://adventofcode.com/2022/day/15/input
-fn dl_input(year: usize, day: usize, agent: &Agent, dl_opt: DLOpt) -> Result<(), String> {
-    let file_loc = format!("inputs/day{day}-input.txt");
-    if dl_opt == DLOpt::IfNoExist {
-        // TODO: check if input file already exists (depending on options)
-        let p = Path::new(&file_loc);
-        if let Ok(exists) = p.try_exists() {
-            if exists == true {
-                println!("(input already exists, skipping auto-download)");
-                return Ok(());
-            }
-        }
-    }
-    let url = format!("https://adventofcode.com/{year}/day/{day}/input");
-    // println!("Input {url} --> {file_loc}");
-    let bytes = url_to_buf(&url, agent)?;
-    match fs::write(file_loc, bytes) {
-        Ok(_) => {}
-        Err(err) => {
-            let err_str = if let Some(inner_err) = err.into_inner() {
-                format!("{inner_err}")
-            } else {
-                format!("Some std::io::Error happened")
-            };
-            return Err(format!("Failed to write file: {err_str}"));
-        }
+// make sure the description page actually has the puzzle in it
+pub fn check_description(bytes: &[u8]) -> Result<(), BadDownload> {
+    let contents = String::from_utf8_lossy(bytes);
+    if contents.trim().is_empty() {
+        Err(BadDownload::Empty)
+    } else if contents.contains("<article class=\"day-desc\">") {
+        Ok(())
+    } else {
+        Err(BadDownload::NoDescription)
     }
-    Ok(())
 }
 
-fn agent_for_dl() -> Result<Agent, String> {
+// (this fails if the session cookie is not setup)
+pub(crate) fn agent_for_dl() -> Result<Agent, String> {
+    let (session_cookie, _) = session_cookie()?;
+    make_agent(session_cookie)
+}
+
+// where the session cookie came from
+pub(crate) enum CookieSource {
+    EnvVar,
+    File(PathBuf),
+}
+
+// the session cookie is read from AOC_SESSION, or from ~/.aoc-session-cookie
+pub(crate) fn session_cookie() -> Result<(String, CookieSource), String> {
+    if let Ok(value) = env::var("AOC_SESSION") {
+        let value = value.trim().to_string();
+        if !value.is_empty() {
+            return Ok((value, CookieSource::EnvVar));
+        }
+    }
     let home_dir = match dirs::home_dir() {
         Some(d) => d,
         None => {
@@ -158,61 +277,43 @@ fn agent_for_dl() -> Result<Agent, String> {
         }
     };
     let cookie_file = home_dir.join(".aoc-session-cookie");
-    let session_cookie = match fs::read_to_string(cookie_file) {
-        Ok(s) => s.trim().to_string(),
+    match fs::read_to_string(&cookie_file) {
+        Ok(s) => Ok((s.trim().to_string(), CookieSource::File(cookie_file))),
         Err(err) => {
             let err_str = if let Some(inner_err) = err.into_inner() {
                 format!("{inner_err}")
             } else {
                 format!("Some std::io::Error happened")
             };
-            return Err(format!("Failed to read session cookie file: {err_str}"));
+            Err(format!("Failed to read session cookie file: {err_str}"))
         }
-    };
-    Ok(make_agent(session_cookie))
+    }
 }
 
-// auto-download the input for the given day
-// (because this is auto, don't fail if session cookie is not setup)
-pub fn auto_download(year: usize, day: usize) -> Result<(), String> {
-    // TODO: extract this stuff, since I'm doing it twice
-    // (well, basically the same thing, but still twice)
-    let home_dir = match dirs::home_dir() {
-        Some(d) => d,
+fn make_agent(session_cookie: String) -> Result<Agent, String> {
+    let base_url = base_url();
+    let url = Url::parse(&base_url).or(Err(format!("could not parse base URL '{}'", base_url)))?;
+    let domain = match url.host_str() {
+        Some(h) => h.to_string(),
         None => {
-            println!("(no home dir, skipping auto-download)");
-            return Ok(());
+            return Err(format!("base URL '{}' has no host", base_url));
         }
     };
-    let cookie_file = home_dir.join(".aoc-session-cookie");
-    let session_cookie = match fs::read_to_string(cookie_file) {
-        Ok(s) => s.trim().to_string(),
-        Err(_) => {
-            println!("(failed to read session cookie file, skipping auto-download)");
-            return Ok(());
-        }
-    };
-    let agent = make_agent(session_cookie);
-    dl_input(year, day, &agent, DLOpt::IfNoExist)
-}
 
-fn make_agent(session_cookie: String) -> Agent {
     // using the values from my signed-in cookie
     // (I don't think I have to set expires/max-age?)
+    // (secure cookies are not sent over plain http, like to a local test server)
     let raw_cookie = RawCookie::build("session", session_cookie)
-        .domain("adventofcode.com")
+        .domain(domain)
         .path("/")
-        .secure(true)
+        .secure(url.scheme() == "https")
         .http_only(true)
         .same_site(SameSite::Lax)
         .max_age(Duration::days(365))
         .finish();
 
     let mut cookies: Vec<Result<Cookie, CookieError>> = Vec::new();
-    cookies.push(Cookie::try_from_raw_cookie(
-        &raw_cookie,
-        &Url::parse("https://adventofcode.com").unwrap(),
-    ));
+    cookies.push(Cookie::try_from_raw_cookie(&raw_cookie, &url));
 
     // true is for 'load expired'
     let cs =
@@ -225,5 +326,8 @@ fn make_agent(session_cookie: String) -> Agent {
     // let cookie_json = std::str::from_utf8(buf.as_slice()).unwrap().to_string();
     // println!("cookies in store: '{}'", cookie_json);
 
-    ureq::builder().cookie_store(cs).build()
+    Ok(ureq::builder()
+        .cookie_store(cs)
+        .user_agent(&governor::user_agent())
+        .build())
 }
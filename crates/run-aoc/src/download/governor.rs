@@ -0,0 +1,83 @@
+use std::fs;
+use std::sync::Once;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::setting;
+
+// every request to the server is logged here, so the interval between them
+// can be enforced across separate runs of the binary (and across years)
+const REQUEST_LOG_FILE: &str = ".aoc-request-log";
+// only the most recent requests are kept
+const MAX_LOG_ENTRIES: usize = 100;
+// minimum time between requests, can be changed with AOC_REQUEST_INTERVAL (in seconds)
+const DEFAULT_INTERVAL_SECS: u64 = 5;
+
+// wait until enough time has passed since the last request, then log this one
+pub(crate) fn throttle(method: &str, url: &str) -> Result<(), String> {
+    let home_dir = match dirs::home_dir() {
+        Some(d) => d,
+        None => {
+            return Err(String::from("you have no home directory!?"));
+        }
+    };
+    let log_file = home_dir.join(REQUEST_LOG_FILE);
+    let log = fs::read_to_string(&log_file).unwrap_or_default();
+    let mut entries: Vec<&str> = log.lines().filter(|l| !l.trim().is_empty()).collect();
+
+    let interval = Duration::from_secs(
+        match setting("AOC_REQUEST_INTERVAL", ".aoc-request-interval") {
+            Some(s) => s.parse().or(Err(format!(
+                "could not parse request interval '{}' as a number",
+                s
+            )))?,
+            None => DEFAULT_INTERVAL_SECS,
+        },
+    );
+    let last_request = entries
+        .last()
+        .and_then(|l| l.split('\t').next())
+        .and_then(|millis| millis.parse::<u64>().ok())
+        .map(Duration::from_millis);
+    if let Some(last) = last_request {
+        let since_last = now().saturating_sub(last);
+        if since_last < interval {
+            let wait = interval - since_last;
+            println!("(waiting {:.1?} between requests)", wait);
+            thread::sleep(wait);
+        }
+    }
+
+    let entry = format!("{}\t{}\t{}", now().as_millis(), method, url);
+    entries.push(&entry);
+    let skip = entries.len().saturating_sub(MAX_LOG_ENTRIES);
+    let contents: String = entries[skip..].iter().map(|e| format!("{e}\n")).collect();
+    match fs::write(&log_file, contents) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(format!("Failed to write request log: {err}")),
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+}
+
+// the user agent should include contact info, so the AoC maintainers can get in touch
+// (set with AOC_USER_AGENT, or in ~/.aoc-user-agent)
+pub(crate) fn user_agent() -> String {
+    let base = "github.com/mikrostew/advent-of-code run-aoc";
+    match setting("AOC_USER_AGENT", ".aoc-user-agent") {
+        Some(contact) => format!("{base} (contact: {contact})"),
+        None => {
+            // (only once, not for every request)
+            MISSING_CONTACT.call_once(|| {
+                println!("warning: no contact info for the User-Agent, please set AOC_USER_AGENT or save it in ~/.aoc-user-agent (like an email address or GitHub username)");
+            });
+            String::from(base)
+        }
+    }
+}
+
+static MISSING_CONTACT: Once = Once::new();
@@ -0,0 +1,172 @@
+use std::fs;
+use std::path::Path;
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use crate::config::setting;
+
+// encrypted inputs can be committed to a public repo, and the plaintext ones ignored
+// (AoC asks people not to share their inputs)
+
+const KEY_FILE: &str = ".aoc-inputs-key";
+// at the start of every encrypted file, to recognize it (and the format)
+const MAGIC: &[u8] = b"aoc-enc-v1\n";
+const NONCE_LEN: usize = 12;
+const INPUTS_DIR: &str = "inputs";
+
+// the encrypted version of an input file is next to it
+pub fn encrypted_path(file_loc: &str) -> String {
+    format!("{file_loc}.enc")
+}
+
+// the key is 64 hex characters, from AOC_INPUTS_KEY or ~/.aoc-inputs-key
+fn load_key() -> Result<Option<Key>, String> {
+    match setting("AOC_INPUTS_KEY", KEY_FILE) {
+        Some(hex) => {
+            let bytes = from_hex(&hex).ok_or(format!(
+                "the inputs key should be 64 hex characters (in AOC_INPUTS_KEY or ~/{KEY_FILE})"
+            ))?;
+            Ok(Some(*Key::from_slice(&bytes)))
+        }
+        None => Ok(None),
+    }
+}
+
+fn key() -> Result<Key, String> {
+    load_key()?.ok_or(format!(
+        "no inputs key (set AOC_INPUTS_KEY, or save it in ~/{KEY_FILE})"
+    ))
+}
+
+// make a new key the first time anything is encrypted
+fn key_or_create() -> Result<Key, String> {
+    if let Some(key) = load_key()? {
+        return Ok(key);
+    }
+    let home_dir = dirs::home_dir().ok_or(String::from("you have no home directory!?"))?;
+    let key_file = home_dir.join(KEY_FILE);
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    fs::write(&key_file, format!("{}\n", to_hex(&key)))
+        .map_err(|e| format!("Failed to save the inputs key: {e}"))?;
+    restrict_permissions(&key_file);
+    println!(
+        "created a new inputs key in '{}' (back it up, the inputs can't be decrypted without it)",
+        key_file.display()
+    );
+    Ok(key)
+}
+
+// (only the owner should be able to read the key)
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) {}
+
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = ChaCha20Poly1305::new(key);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .or(Err(String::from("encryption failed")))?;
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&nonce);
+    bytes.extend_from_slice(&ciphertext);
+    Ok(bytes)
+}
+
+pub fn decrypt(key: &Key, bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let rest = bytes
+        .strip_prefix(MAGIC)
+        .ok_or(String::from("not an encrypted input file"))?;
+    if rest.len() < NONCE_LEN {
+        return Err(String::from("encrypted input file is truncated"));
+    }
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let cipher = ChaCha20Poly1305::new(key);
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .or(Err(String::from(
+            "decryption failed (wrong key, or the file was changed)",
+        )))
+}
+
+// read the decrypted contents of the encrypted version of this file
+pub fn read_encrypted(file_loc: &str) -> Result<String, String> {
+    let enc_file = encrypted_path(file_loc);
+    let bytes = fs::read(&enc_file).map_err(|e| format!("Failed to read '{enc_file}': {e}"))?;
+    let plaintext = decrypt(&key()?, &bytes).map_err(|e| format!("'{enc_file}': {e}"))?;
+    String::from_utf8(plaintext).or(Err(format!("'{enc_file}' is not valid UTF-8")))
+}
+
+// the real inputs (not the examples, those are in the puzzle descriptions)
+fn input_files(suffix: &str) -> Result<Vec<String>, String> {
+    let entries = fs::read_dir(INPUTS_DIR).map_err(|e| format!("Failed to read inputs: {e}"))?;
+    let mut files: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| format!("{INPUTS_DIR}/{}", e.file_name().to_string_lossy()))
+        .filter(|f| f.ends_with(suffix))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+// encrypt every input, unless it's already encrypted with the same contents
+// (the nonce is random, so encrypting again would change every file)
+pub fn encrypt_inputs() -> Result<(), String> {
+    let key = key_or_create()?;
+    let mut num_encrypted = 0;
+    for file in input_files("-input.txt")? {
+        let plaintext = fs::read(&file).map_err(|e| format!("Failed to read '{file}': {e}"))?;
+        let enc_file = encrypted_path(&file);
+        if let Ok(existing) = fs::read(&enc_file) {
+            if decrypt(&key, &existing).ok().as_ref() == Some(&plaintext) {
+                continue;
+            }
+        }
+        let bytes = encrypt(&key, &plaintext)?;
+        fs::write(&enc_file, bytes).map_err(|e| format!("Failed to write '{enc_file}': {e}"))?;
+        println!("{file} --> {enc_file}");
+        num_encrypted += 1;
+    }
+    println!("encrypted {num_encrypted} input(s)");
+    Ok(())
+}
+
+// decrypt every encrypted input that doesn't have a plaintext version
+pub fn decrypt_inputs() -> Result<(), String> {
+    let key = key()?;
+    let mut num_decrypted = 0;
+    for enc_file in input_files(".txt.enc")? {
+        let file = enc_file.trim_end_matches(".enc");
+        if Path::new(file).exists() {
+            continue;
+        }
+        let bytes = fs::read(&enc_file).map_err(|e| format!("Failed to read '{enc_file}': {e}"))?;
+        let plaintext = decrypt(&key, &bytes).map_err(|e| format!("'{enc_file}': {e}"))?;
+        fs::write(file, plaintext).map_err(|e| format!("Failed to write '{file}': {e}"))?;
+        println!("{enc_file} --> {file}");
+        num_decrypted += 1;
+    }
+    println!("decrypted {num_decrypted} input(s)");
+    Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() != 64 || !s.is_ascii() {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
@@ -0,0 +1,379 @@
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::cli::Part;
+
+// one file per year, in the year directory (next to descriptions/ and inputs/)
+const HISTORY_FILE: &str = "run-history.txt";
+
+// one line per run, tab-separated
+#[derive(Debug)]
+pub struct RunRecord {
+    pub timestamp: u64,
+    pub day: usize,
+    pub part: Part,
+    pub input_file: String,
+    pub profile: String,
+    pub commit: String,
+    pub duration: Duration,
+    pub answer: String,
+}
+
+impl RunRecord {
+    pub fn new(day: usize, part: Part, input_file: &str, answer: &str, duration: Duration) -> Self {
+        RunRecord {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            day,
+            part,
+            input_file: input_file.to_string(),
+            profile: build_profile().to_string(),
+            commit: git_commit().unwrap_or(String::from("-")),
+            duration,
+            answer: answer.to_string(),
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            self.timestamp,
+            self.day,
+            self.part,
+            self.input_file,
+            self.profile,
+            self.commit,
+            self.duration.as_nanos(),
+            escape_answer(&self.answer),
+        )
+    }
+}
+
+impl FromStr for RunRecord {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 8 {
+            return Err(format!(
+                "expected 8 fields in history line, found {}",
+                fields.len()
+            ));
+        }
+        let parse_num = |s: &str| {
+            s.parse::<u64>()
+                .or(Err(format!("could not parse '{}' as a number", s)))
+        };
+        Ok(RunRecord {
+            timestamp: parse_num(fields[0])?,
+            day: parse_num(fields[1])? as usize,
+            part: fields[2].parse()?,
+            input_file: fields[3].to_string(),
+            profile: fields[4].to_string(),
+            commit: fields[5].to_string(),
+            duration: Duration::from_nanos(parse_num(fields[6])?),
+            answer: unescape_answer(fields[7]),
+        })
+    }
+}
+
+// some answers are multi-line (like the CRT output), so keep them on one line
+fn escape_answer(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape_answer(s: &str) -> String {
+    let mut answer = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => answer.push('\n'),
+                Some('t') => answer.push('\t'),
+                Some(other) => answer.push(other),
+                None => answer.push('\\'),
+            }
+        } else {
+            answer.push(c);
+        }
+    }
+    answer
+}
+
+// the runner is compiled with the same profile as the binary using it
+fn build_profile() -> &'static str {
+    if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    }
+}
+
+// (this is optional, so any failure just means there's no commit)
+fn git_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    // mark it if there are uncommitted changes, since those are usually what's being measured
+    let dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .map(|o| !o.stdout.is_empty())
+        .unwrap_or(false);
+    if dirty {
+        Some(format!("{commit}+dirty"))
+    } else {
+        Some(commit)
+    }
+}
+
+pub fn record(rec: &RunRecord) -> Result<(), String> {
+    let mut file = match OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE)
+    {
+        Ok(f) => f,
+        Err(err) => {
+            return Err(format!("Failed to open history file: {err}"));
+        }
+    };
+    match file.write_all(rec.to_line().as_bytes()) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(format!("Failed to write history file: {err}")),
+    }
+}
+
+fn read_history() -> Result<Vec<RunRecord>, String> {
+    let contents = match fs::read_to_string(HISTORY_FILE) {
+        Ok(s) => s,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => {
+            return Err(format!("Failed to read history file: {err}"));
+        }
+    };
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .enumerate()
+        .map(|(i, l)| {
+            l.parse::<RunRecord>()
+                .map_err(|e| format!("{HISTORY_FILE} line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+// show all recorded runs for this day/part/input, and flag the slow ones
+// (times are only compared against runs with the same build profile)
+pub fn show_history(
+    day: usize,
+    part: Part,
+    input_file: &str,
+    threshold_pct: f64,
+) -> Result<(), String> {
+    let runs: Vec<RunRecord> = read_history()?
+        .into_iter()
+        .filter(|r| r.day == day && r.part == part && r.input_file == input_file)
+        .collect();
+    if runs.is_empty() {
+        println!("No recorded runs for {input_file}, part {part}");
+        return Ok(());
+    }
+
+    println!("Runs for {input_file}, part {part} (flagging > {threshold_pct}% slower than best)\n");
+    println!(
+        "{:<19}  {:<7}  {:<14}  {:>12}  {:>9}  answer",
+        "time (UTC)", "profile", "commit", "duration", "vs best"
+    );
+    let mut num_flagged = 0;
+    let mut prev_answer: Option<&str> = None;
+    for run in runs.iter() {
+        let best = runs
+            .iter()
+            .filter(|r| r.profile == run.profile)
+            .map(|r| r.duration)
+            .min()
+            .expect("at least this run has the same profile");
+        let pct_slower = if best.is_zero() {
+            0.0
+        } else {
+            (run.duration.as_secs_f64() / best.as_secs_f64() - 1.0) * 100.0
+        };
+        let flag = if pct_slower > threshold_pct {
+            num_flagged += 1;
+            "  <-- SLOWER"
+        } else {
+            ""
+        };
+        let answer_note = match prev_answer {
+            Some(prev) if prev != run.answer => " (answer changed!)",
+            _ => "",
+        };
+        prev_answer = Some(&run.answer);
+        println!(
+            "{:<19}  {:<7}  {:<14}  {:>12}  {:>8.1}%  {}{}{}",
+            format_timestamp(run.timestamp),
+            run.profile,
+            run.commit,
+            format!("{:.3?}", run.duration),
+            pct_slower,
+            first_line(&run.answer),
+            answer_note,
+            flag,
+        );
+    }
+    println!(
+        "\n{} run(s), {} flagged as more than {}% slower than the best",
+        runs.len(),
+        num_flagged,
+        threshold_pct
+    );
+    Ok(())
+}
+
+// multi-line answers get abbreviated in the table
+fn first_line(answer: &str) -> String {
+    let mut lines = answer.trim().lines();
+    match (lines.next(), lines.next()) {
+        (Some(first), Some(_)) => format!("{first} (...)"),
+        (Some(first), None) => first.to_string(),
+        (None, _) => String::new(),
+    }
+}
+
+// format seconds since the epoch as 'YYYY-MM-DD HH:MM:SS' (UTC)
+pub(crate) fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let (y, m, d) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        y,
+        m,
+        d,
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60
+    )
+}
+
+// days since 1970-01-01 to (year, month, day)
+// (from http://howardhinnant.github.io/date_algorithms.html)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
+
+// (year, month, day) to days since 1970-01-01
+// (the inverse of civil_from_days, from the same place)
+pub(crate) fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = if m > 2 { m - 3 } else { m + 9 } as i64;
+    let doy = (153 * mp + 2) / 5 + d as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_round_trip() {
+        for answer in [
+            "123",
+            "a\tb",
+            "#..#\n.##.\n",
+            "back\\slash",
+            "\\n not a newline",
+            "\\",
+        ] {
+            let escaped = escape_answer(answer);
+            assert!(!escaped.contains('\t') && !escaped.contains('\n'));
+            assert_eq!(unescape_answer(&escaped), answer);
+        }
+        assert_eq!(escape_answer("a\\b\tc\nd"), "a\\\\b\\tc\\nd");
+        // (a trailing backslash is kept)
+        assert_eq!(unescape_answer("abc\\"), "abc\\");
+    }
+
+    #[test]
+    fn record_line_round_trip() {
+        let rec = RunRecord {
+            timestamp: 1670000000,
+            day: 10,
+            part: Part::Two,
+            input_file: String::from("input"),
+            profile: String::from("release"),
+            commit: String::from("abc1234+dirty"),
+            duration: Duration::from_nanos(1_234_567),
+            answer: String::from("#..#\n.##."),
+        };
+        let line = rec.to_line();
+        let parsed: RunRecord = line.trim_end_matches('\n').parse().unwrap();
+        assert_eq!(parsed.to_line(), line);
+        assert_eq!(parsed.answer, "#..#\n.##.");
+        assert_eq!(parsed.duration, Duration::from_nanos(1_234_567));
+    }
+
+    #[test]
+    fn malformed_lines() {
+        let err = |line: &str| line.parse::<RunRecord>().unwrap_err();
+        assert_eq!(
+            err("1670000000\t10\ttwo\tinput"),
+            "expected 8 fields in history line, found 4"
+        );
+        assert_eq!(
+            err("soon\t10\ttwo\tinput\trelease\t-\t100\t5"),
+            "could not parse 'soon' as a number"
+        );
+        assert_eq!(
+            err("1670000000\t10\ttwo\tinput\trelease\t-\t1.5\t5"),
+            "could not parse '1.5' as a number"
+        );
+        assert!(err("1670000000\t10\tthree\tinput\trelease\t-\t100\t5").contains("three"));
+    }
+
+    #[test]
+    fn civil_dates() {
+        for (days, date) in [
+            (0, (1970, 1, 1)),
+            (-1, (1969, 12, 31)),
+            (11_016, (2000, 2, 29)),
+            (19_327, (2022, 12, 1)),
+            (19_782, (2024, 2, 29)),
+        ] {
+            assert_eq!(civil_from_days(days), date);
+            assert_eq!(days_from_civil(date.0, date.1, date.2), days);
+        }
+        for days in -1000..40_000 {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+        assert_eq!(format_timestamp(1_669_870_800), "2022-12-01 05:00:00");
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+// problems with input files that can break the solution parsers
+// (these are reported before running, but don't stop the run)
+#[derive(Debug, Eq, PartialEq)]
+pub enum InputWarning {
+    Empty,
+    CrlfLineEndings,
+    TrailingWhitespace {
+        num_lines: usize,
+        first_line: usize,
+    },
+    MissingFinalNewline,
+    LooksTruncated {
+        line: usize,
+        len: usize,
+        expected_len: usize,
+    },
+    LooksLikeHtml,
+    LoginMessage,
+}
+
+impl fmt::Display for InputWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputWarning::Empty => write!(f, "input file is empty"),
+            InputWarning::CrlfLineEndings => write!(
+                f,
+                "input has CRLF line endings (converted to LF, unless the day uses #[runner_fn(raw_input)])"
+            ),
+            InputWarning::TrailingWhitespace {
+                num_lines,
+                first_line,
+            } => write!(
+                f,
+                "{} line(s) have trailing whitespace (first one is line {})",
+                num_lines, first_line
+            ),
+            InputWarning::MissingFinalNewline => {
+                write!(f, "input does not end with a newline")
+            }
+            InputWarning::LooksTruncated {
+                line,
+                len,
+                expected_len,
+            } => write!(
+                f,
+                "input looks truncated: line {} has length {}, but every other line has length {}",
+                line, len, expected_len
+            ),
+            InputWarning::LooksLikeHtml => write!(
+                f,
+                "input looks like HTML (maybe a web page was saved instead of the input?)"
+            ),
+            InputWarning::LoginMessage => write!(
+                f,
+                "input is the 'please log in' message (the session cookie is missing or expired)"
+            ),
+        }
+    }
+}
+
+// read an input file, or the encrypted version of it if there's no plaintext
+pub fn read_input(file_loc: &str) -> Result<String, String> {
+    if !Path::new(file_loc).exists()
+        && Path::new(&crate::encrypt::encrypted_path(file_loc)).exists()
+    {
+        return crate::encrypt::read_encrypted(file_loc);
+    }
+    match fs::read_to_string(file_loc) {
+        Ok(s) => Ok(s),
+        Err(err) => {
+            let err_str = if let Some(inner_err) = err.into_inner() {
+                format!("{inner_err}")
+            } else {
+                String::from("Some std::io::Error happened")
+            };
+            Err(format!("Failed to read file: {err_str}"))
+        }
+    }
+}
+
+// look for things that are probably wrong with the input
+pub fn check(contents: &str) -> Vec<InputWarning> {
+    let mut warnings = vec![];
+    if contents.is_empty() {
+        warnings.push(InputWarning::Empty);
+        return warnings;
+    }
+    if contents.contains("Puzzle inputs differ by user") {
+        warnings.push(InputWarning::LoginMessage);
+    } else if looks_like_html(contents) {
+        warnings.push(InputWarning::LooksLikeHtml);
+    }
+    if contents.contains("\r\n") {
+        warnings.push(InputWarning::CrlfLineEndings);
+    }
+
+    let lines: Vec<&str> = contents.lines().collect();
+    let trailing: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.ends_with([' ', '\t']))
+        .map(|(i, _)| i + 1)
+        .collect();
+    if !trailing.is_empty() {
+        warnings.push(InputWarning::TrailingWhitespace {
+            num_lines: trailing.len(),
+            first_line: trailing[0],
+        });
+    }
+
+    if !contents.ends_with('\n') {
+        warnings.push(InputWarning::MissingFinalNewline);
+    }
+    if let Some(w) = check_truncated(&lines) {
+        warnings.push(w);
+    }
+    warnings
+}
+
+fn looks_like_html(contents: &str) -> bool {
+    let start = contents.trim_start().to_lowercase();
+    start.starts_with("<!doctype html") || start.starts_with("<html") || start.contains("<body")
+}
+
+// for grid-like inputs, where every line has the same length,
+// a shorter last line usually means the download (or copy-paste) was cut off
+fn check_truncated(lines: &[&str]) -> Option<InputWarning> {
+    if lines.len() < 3 {
+        return None;
+    }
+    let (last, rest) = lines.split_last()?;
+    let expected_len = rest[0].len();
+    if expected_len == 0 || rest.iter().any(|l| l.len() != expected_len) {
+        return None;
+    }
+    if last.len() < expected_len {
+        Some(InputWarning::LooksTruncated {
+            line: lines.len(),
+            len: last.len(),
+            expected_len,
+        })
+    } else {
+        None
+    }
+}
+
+// convert CRLF (and lone CR) line endings to LF
+pub fn normalize_line_endings(contents: String) -> String {
+    if contents.contains('\r') {
+        contents.replace("\r\n", "\n").replace('\r', "\n")
+    } else {
+        contents
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn good_input() {
+        assert_eq!(check("1000\n2000\n\n3000\n"), vec![]);
+        assert_eq!(check("#..#\n.##.\n#..#\n"), vec![]);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(check(""), vec![InputWarning::Empty]);
+    }
+
+    #[test]
+    fn crlf() {
+        assert_eq!(check("abc\r\ndef\r\n"), vec![InputWarning::CrlfLineEndings]);
+        assert_eq!(
+            normalize_line_endings(String::from("abc\r\ndef\rghi\n")),
+            "abc\ndef\nghi\n"
+        );
+        assert_eq!(normalize_line_endings(String::from("abc\n")), "abc\n");
+    }
+
+    #[test]
+    fn missing_final_newline() {
+        assert_eq!(check("1000\n2000"), vec![InputWarning::MissingFinalNewline]);
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        assert_eq!(
+            check("a\nb \nc\t\n"),
+            vec![InputWarning::TrailingWhitespace {
+                num_lines: 2,
+                first_line: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        assert_eq!(
+            check("#..#\n.##.\n#."),
+            vec![
+                InputWarning::MissingFinalNewline,
+                InputWarning::LooksTruncated {
+                    line: 3,
+                    len: 2,
+                    expected_len: 4
+                }
+            ]
+        );
+        let lines = |s: &'static str| s.lines().collect::<Vec<&str>>();
+        // (not enough lines, or not a grid)
+        assert_eq!(check_truncated(&lines("abcd\nab")), None);
+        assert_eq!(check_truncated(&lines("abcd\nabc\nab")), None);
+        assert_eq!(check_truncated(&lines("\n\nab")), None);
+        assert_eq!(check_truncated(&lines("abcd\nabcd\nabcd")), None);
+    }
+
+    #[test]
+    fn not_the_input() {
+        assert_eq!(
+            check("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            vec![InputWarning::LoginMessage]
+        );
+        assert_eq!(
+            check("<!DOCTYPE html>\n<html lang=\"en-us\">\n</html>\n"),
+            vec![InputWarning::LooksLikeHtml]
+        );
+    }
+}
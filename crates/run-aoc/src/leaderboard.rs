@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::fs;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+
+use crate::download::{agent_for_dl, base_url, url_to_buf};
+use crate::history::format_timestamp;
+
+// the leaderboard shouldn't be fetched more often than this
+// (from the note on the private leaderboard page)
+const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "unknown format '{}', expected table or json",
+                input
+            )),
+        }
+    }
+}
+
+// the parts of the JSON that are used here
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    // (null for anonymous users)
+    pub name: Option<String>,
+    pub stars: usize,
+    pub local_score: u64,
+    pub last_star_ts: u64,
+    // day -> part -> when it was completed
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: u64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(n) => n.to_string(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    // when this part was completed (if it was)
+    pub fn completed(&self, day: usize, part: usize) -> Option<u64> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+            .map(|s| s.get_star_ts)
+    }
+
+    fn stars_for_day(&self, day: usize) -> usize {
+        (1..=2)
+            .filter(|p| self.completed(day, *p).is_some())
+            .count()
+    }
+}
+
+// cached in the year directory, one file per leaderboard
+fn cache_file(id: &str) -> String {
+    format!("leaderboard-{id}.json")
+}
+
+// use the cached JSON if it's recent enough, otherwise fetch it again
+pub fn fetch(year: usize, id: &str) -> Result<String, String> {
+    let file = cache_file(id);
+    if let Some(age) = cache_age(&file) {
+        if age < REFRESH_INTERVAL {
+            println!(
+                "(using cached leaderboard from {}m {}s ago)",
+                age.as_secs() / 60,
+                age.as_secs() % 60
+            );
+            return fs::read_to_string(&file)
+                .map_err(|e| format!("Failed to read cached leaderboard: {e}"));
+        }
+    }
+
+    let url = format!("{}/{year}/leaderboard/private/view/{id}.json", base_url());
+    println!("{url} --> {file}");
+    let agent = agent_for_dl()?;
+    let bytes = url_to_buf(&url, &agent)?;
+    let json = match String::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(_) => {
+            return Err(String::from("leaderboard is not valid UTF-8"));
+        }
+    };
+    // (the server redirects to the login page if the session is not valid)
+    parse(&json)?;
+    if let Err(err) = fs::write(&file, &json) {
+        println!("(failed to cache the leaderboard: {err})");
+    }
+    Ok(json)
+}
+
+fn cache_age(file: &str) -> Option<Duration> {
+    let modified = fs::metadata(file).ok()?.modified().ok()?;
+    SystemTime::now().duration_since(modified).ok()
+}
+
+pub fn parse(json: &str) -> Result<Leaderboard, String> {
+    serde_json::from_str(json).map_err(|e| format!("could not parse leaderboard JSON: {e}"))
+}
+
+pub fn show(year: usize, id: &str, format: Format, day: Option<usize>) -> Result<(), String> {
+    let json = fetch(year, id)?;
+    match format {
+        Format::Json => println!("{}", json),
+        Format::Table => {
+            let leaderboard = parse(&json)?;
+            match day {
+                Some(d) => print!("{}", day_table(&leaderboard, d)),
+                None => print!("{}", table(&leaderboard)),
+            }
+        }
+    }
+    Ok(())
+}
+
+// ordered by local score, then by who got their last star first
+fn ranked(leaderboard: &Leaderboard) -> Vec<&Member> {
+    let mut members: Vec<&Member> = leaderboard.members.values().collect();
+    members.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(a.last_star_ts.cmp(&b.last_star_ts))
+            .then(a.id.cmp(&b.id))
+    });
+    members
+}
+
+// stars per day are '*' for both parts, '+' for part 1 only, and '.' for none
+pub fn table(leaderboard: &Leaderboard) -> String {
+    let mut out = format!(
+        "{:>4}  {:>5}  {:>5}  {:<25}  {:<19}  name\n",
+        "rank", "score", "stars", "1        10        20   25", "last star (UTC)"
+    );
+    for (i, member) in ranked(leaderboard).iter().enumerate() {
+        let days: String = (1..=25)
+            .map(|d| match member.stars_for_day(d) {
+                2 => '*',
+                1 => '+',
+                _ => '.',
+            })
+            .collect();
+        let last_star = if member.last_star_ts == 0 {
+            String::from("-")
+        } else {
+            format_timestamp(member.last_star_ts)
+        };
+        out.push_str(&format!(
+            "{:>4}  {:>5}  {:>5}  {}  {:<19}  {}\n",
+            i + 1,
+            member.local_score,
+            member.stars,
+            days,
+            last_star,
+            member.display_name()
+        ));
+    }
+    out
+}
+
+// when each member completed both parts of one day, fastest first
+pub fn day_table(leaderboard: &Leaderboard, day: usize) -> String {
+    let mut members = ranked(leaderboard);
+    members.sort_by_key(|m| {
+        (
+            m.completed(day, 2).unwrap_or(u64::MAX),
+            m.completed(day, 1).unwrap_or(u64::MAX),
+        )
+    });
+    let mut out = format!(
+        "Day {day}\n\n{:>4}  {:<19}  {:<19}  name\n",
+        "rank", "part 1 (UTC)", "part 2 (UTC)"
+    );
+    let show_ts = |ts: Option<u64>| ts.map(format_timestamp).unwrap_or(String::from("-"));
+    for (i, member) in members.iter().enumerate() {
+        out.push_str(&format!(
+            "{:>4}  {:<19}  {:<19}  {}\n",
+            i + 1,
+            show_ts(member.completed(day, 1)),
+            show_ts(member.completed(day, 2)),
+            member.display_name()
+        ));
+    }
+    out
+}
@@ -0,0 +1,165 @@
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cli::Part;
+use crate::history::format_timestamp;
+use crate::submit::Verdict;
+
+// every submitted answer, one file per year (next to descriptions/ and inputs/)
+const LEDGER_FILE: &str = "guesses.txt";
+
+// one line per submission, tab-separated
+struct Guess {
+    timestamp: u64,
+    day: usize,
+    part: Part,
+    answer: String,
+    verdict: Verdict,
+}
+
+impl Guess {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            self.timestamp,
+            self.day,
+            self.part,
+            self.answer,
+            self.verdict.key(),
+        )
+    }
+
+    fn from_line(line: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "expected 5 fields in guess line, found {}",
+                fields.len()
+            ));
+        }
+        Ok(Guess {
+            timestamp: fields[0]
+                .parse()
+                .or(Err(format!("could not parse '{}' as a number", fields[0])))?,
+            day: fields[1]
+                .parse()
+                .or(Err(format!("could not parse '{}' as a number", fields[1])))?,
+            part: fields[2].parse()?,
+            answer: fields[3].to_string(),
+            verdict: Verdict::from_key(fields[4])
+                .ok_or(format!("unknown verdict '{}'", fields[4]))?,
+        })
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "'{}' was submitted at {} UTC ({})",
+            self.answer,
+            format_timestamp(self.timestamp),
+            self.verdict
+        )
+    }
+}
+
+fn read_guesses(day: usize, part: Part) -> Result<Vec<Guess>, String> {
+    let contents = match fs::read_to_string(LEDGER_FILE) {
+        Ok(s) => s,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => {
+            return Err(format!("Failed to read guess ledger: {err}"));
+        }
+    };
+    let mut guesses = vec![];
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let guess =
+            Guess::from_line(line).map_err(|e| format!("{LEDGER_FILE} line {}: {e}", i + 1))?;
+        if guess.day == day && guess.part == part {
+            guesses.push(guess);
+        }
+    }
+    Ok(guesses)
+}
+
+// make sure this answer is worth submitting, based on earlier verdicts
+// (the error explains why it isn't)
+pub fn check(day: usize, part: Part, answer: &str) -> Result<(), String> {
+    let guesses = read_guesses(day, part)?;
+
+    if let Some(correct) = guesses.iter().find(|g| g.verdict == Verdict::Correct) {
+        return Err(format!(
+            "day {day} part {part} is already solved: {}",
+            correct.describe()
+        ));
+    }
+    if let Some(same) = guesses
+        .iter()
+        .find(|g| g.answer == answer && is_wrong(&g.verdict))
+    {
+        return Err(format!("not submitting, {}", same.describe()));
+    }
+
+    // numeric answers have to be between the closest 'too low' and 'too high' answers
+    if let Ok(num) = answer.parse::<i128>() {
+        let numeric = |v: Verdict| {
+            guesses
+                .iter()
+                .filter(move |g| g.verdict == v)
+                .filter_map(|g| g.answer.parse::<i128>().ok().map(|n| (n, g)))
+        };
+        if let Some((_, g)) = numeric(Verdict::TooHigh)
+            .filter(|(n, _)| num >= *n)
+            .min_by_key(|(n, _)| *n)
+        {
+            return Err(format!(
+                "not submitting '{answer}', it can't be right because {}",
+                g.describe()
+            ));
+        }
+        if let Some((_, g)) = numeric(Verdict::TooLow)
+            .filter(|(n, _)| num <= *n)
+            .max_by_key(|(n, _)| *n)
+        {
+            return Err(format!(
+                "not submitting '{answer}', it can't be right because {}",
+                g.describe()
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn is_wrong(v: &Verdict) -> bool {
+    matches!(v, Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow)
+}
+
+pub fn record(day: usize, part: Part, answer: &str, verdict: Verdict) -> Result<(), String> {
+    let guess = Guess {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict,
+    };
+    let mut file = match OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(LEDGER_FILE)
+    {
+        Ok(f) => f,
+        Err(err) => {
+            return Err(format!("Failed to open guess ledger: {err}"));
+        }
+    };
+    match file.write_all(guess.to_line().as_bytes()) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(format!("Failed to write guess ledger: {err}")),
+    }
+}
@@ -2,9 +2,21 @@
 pub use aoc_proc::runner_fn;
 pub use aoc_proc::test_fn;
 
+pub mod answers;
+pub mod auth;
 pub mod cli;
+mod config;
 pub mod download;
-mod parse;
+pub mod encrypt;
+pub mod history;
+pub mod input;
+pub mod leaderboard;
+pub mod ledger;
+pub mod parse;
+pub mod prop;
+pub mod puzzle;
+pub mod status;
+pub mod submit;
 
 // generate functions & macros needed in main.rs
 #[macro_export]
@@ -28,18 +40,104 @@ macro_rules! aoc_cli {
                     let day_fn = fn_for_day(parsed_args.0, parsed_args.1)?;
                     // TODO: maybe just show the error, but don't fail?
                     // (because then it prints usage, which is not great)
-                    run_aoc::download::auto_download(year, parsed_args.0)?;
-                    run_aoc::cli::run_day_fn(day_fn, parsed_args.2, parsed_args.3)?;
+                    run_aoc::puzzle::Puzzle::new(year, parsed_args.0).auto_download()?;
+                    let (answer, duration) =
+                        run_aoc::cli::run_day_fn(day_fn, parsed_args.2, parsed_args.3.clone())?;
+                    let record = run_aoc::history::RunRecord::new(
+                        parsed_args.0,
+                        parsed_args.1,
+                        &parsed_args.3,
+                        &answer,
+                        duration,
+                    );
+                    // don't fail the run just because the history couldn't be saved
+                    if let Err(err) = run_aoc::history::record(&record) {
+                        println!("(failed to record run history: {})", err);
+                    }
+                    Ok(())
+                }
+                "submit" => {
+                    let (day, part, answer) = run_aoc::cli::parse_submit_args(&args[1..])?;
+                    let answer = match answer {
+                        Some(a) => a,
+                        None => {
+                            // run the solution with the real input to get the answer
+                            let day_fn = fn_for_day(day, part)?;
+                            let puzzle = run_aoc::puzzle::Puzzle::new(year, day);
+                            puzzle.auto_download()?;
+                            let (answer, _) =
+                                run_aoc::cli::run_day_fn(day_fn, None, puzzle.input_path())?;
+                            answer
+                        }
+                    };
+                    run_aoc::submit::submit(
+                        run_aoc::puzzle::Puzzle::new(year, day),
+                        part,
+                        &answer,
+                    )?;
+                    Ok(())
+                }
+                "history" => {
+                    let (day, part, input_file, threshold) =
+                        run_aoc::cli::parse_history_args(&args[1..])?;
+                    run_aoc::history::show_history(day, part, &input_file, threshold)?;
                     Ok(())
                 }
                 "html" => {
                     let (day, force) = run_aoc::cli::parse_html_args(&args[1..])?;
-                    run_aoc::download::dl_html(year, day, force)?;
+                    run_aoc::puzzle::Puzzle::new(year, day).dl_html(force)?;
                     Ok(())
                 }
                 "md" => {
                     let (day, force) = run_aoc::cli::parse_md_args(&args[1..])?;
-                    run_aoc::download::dl_md(year, day, force)?;
+                    run_aoc::puzzle::Puzzle::new(year, day).update_md(force)?;
+                    Ok(())
+                }
+                "input" => {
+                    let (day, force) = run_aoc::cli::parse_input_args(&args[1..])?;
+                    run_aoc::puzzle::Puzzle::new(year, day).dl_input(force)?;
+                    Ok(())
+                }
+                "inputs" => {
+                    if run_aoc::cli::parse_inputs_args(&args[1..])? {
+                        run_aoc::encrypt::encrypt_inputs()?;
+                    } else {
+                        run_aoc::encrypt::decrypt_inputs()?;
+                    }
+                    Ok(())
+                }
+                "fetch-all" => {
+                    let (from, to) = run_aoc::cli::parse_fetch_all_args(&args[1..])?;
+                    let results = run_aoc::puzzle::fetch_all(year, from, to);
+                    run_aoc::puzzle::print_fetch_report(&results);
+                    Ok(())
+                }
+                "wait" => {
+                    let day = run_aoc::cli::parse_wait_args(&args[1..])?;
+                    let puzzle = run_aoc::puzzle::Puzzle::new(year, day);
+                    puzzle.wait_for_unlock()?;
+                    puzzle.dl_md(run_aoc::download::DLOpt::IfNoExist)?;
+                    puzzle.dl_input(run_aoc::download::DLOpt::IfNoExist)?;
+                    Ok(())
+                }
+                "status" => {
+                    let all_years = run_aoc::cli::parse_status_args(&args[1..])?;
+                    run_aoc::status::show(year, all_years)?;
+                    Ok(())
+                }
+                "leaderboard" => {
+                    let (id, format, day) = run_aoc::cli::parse_leaderboard_args(&args[1..])?;
+                    run_aoc::leaderboard::show(year, &id, format, day)?;
+                    Ok(())
+                }
+                "answers" => {
+                    let day = run_aoc::cli::parse_answers_args(&args[1..])?;
+                    run_aoc::answers::import(year, day)?;
+                    Ok(())
+                }
+                "auth" => {
+                    run_aoc::cli::parse_auth_args(&args[1..])?;
+                    run_aoc::auth::check()?;
                     Ok(())
                 }
                 "help" | "-h" | "--help" => Ok(run_aoc::cli::usage()),
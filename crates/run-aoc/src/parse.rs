@@ -1,8 +1,10 @@
 use std::fmt;
+use std::path::Path;
 
 use lazy_static::lazy_static;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
+use nom::bytes::complete::take_until;
 use nom::bytes::complete::take_until1;
 use nom::character::complete::alpha1;
 use nom::character::complete::alphanumeric1;
@@ -15,16 +17,32 @@ use nom::combinator::map;
 use nom::combinator::map_res;
 use nom::combinator::peek;
 use nom::combinator::recognize;
+use nom::error::ErrorKind;
 use nom::multi::count;
+use nom::multi::many0;
 use nom::multi::many1;
 use nom::multi::many_m_n;
 use nom::multi::separated_list1;
 use nom::sequence::delimited;
 use nom::sequence::preceded;
 use nom::sequence::separated_pair;
+use nom::sequence::terminated;
 use nom::sequence::tuple;
 use nom::IResult;
 use regex::Regex;
+use utils::nom_u64;
+
+use crate::puzzle::Puzzle;
+
+mod description;
+mod entities;
+mod lenient;
+
+pub use description::{CodeBlock, Description, Section};
+
+// links to other pages on the site
+// (not the base URL setting, these are for people reading the markdown)
+const AOC_URL: &str = "https://adventofcode.com";
 
 lazy_static! {
     static ref MATCH_WHITESPACE: Regex = Regex::new(r"\s+").unwrap();
@@ -32,6 +50,7 @@ lazy_static! {
 
 enum Element<'a> {
     H2(&'a str),
+    H3(&'a str),
     Paragraph(Vec<Element<'a>>),
     Text(&'a str),
     InlineCode(Vec<InlineCodeElement<'a>>),
@@ -41,11 +60,19 @@ enum Element<'a> {
     EmStar(&'a str),
     LinkRelative(&'a str, &'a str),
     LinkAbsolute(&'a str, &'a str),
-    UnorderedList(Vec<Element<'a>>),
-    ListItem(Vec<Element<'a>>),
+    Sup(Vec<Element<'a>>),
+    Strikethrough(Vec<Element<'a>>),
+    Italic(Vec<Element<'a>>),
+    // (the first number if it's ordered, and the contents of each item)
+    List(Option<u64>, Vec<Vec<Element<'a>>>),
+    Blockquote(Vec<Element<'a>>),
+    // rows of cells
+    Table(Vec<Vec<Vec<Element<'a>>>>),
     CodeBlock(Vec<CodeBlockElement<'a>>),
     ParagraphSuccess(&'a str),
     Form(Vec<FormElement<'a>>),
+    // (something that wasn't recognized, in lenient mode)
+    RawHtml(&'a str),
     Discard,
 }
 
@@ -66,140 +93,211 @@ enum FormElement<'a> {
 }
 
 // convert to markdown formatting
-impl fmt::Display for Element<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+// (links need to know which puzzle the description is for)
+impl Element<'_> {
+    fn to_md(&self, puzzle: &Puzzle) -> String {
+        let to_md = |vm: &[Element]| vm.iter().map(|m| m.to_md(puzzle)).collect::<String>();
         match self {
-            Element::H2(s) => write!(f, "# {}\n\n", s.trim()),
-            Element::Paragraph(vec_of_md) => write!(
-                f,
-                "{}\n\n",
-                vec_of_md
-                    .into_iter()
-                    .map(|m| m.to_string())
-                    .collect::<String>()
-                    .trim()
-            ),
-            Element::Text(s) => write!(f, "{}", normalize_whitespace(s)),
-            Element::InlineCode(vice) => {
-                write!(
-                    f,
-                    "{}",
-                    match vice.len() {
-                        1 => match &vice[0] {
-                            InlineCodeElement::Text(s) =>
-                                format!("`{}`", replace_html_char_codes(s)),
-                            InlineCodeElement::Em(s) =>
-                                format!("**`{}`**", replace_html_char_codes(s)),
-                        },
-                        _ => format!(
-                            "<code>{}</code>",
-                            vice.into_iter()
-                                .map(|m| match m {
-                                    InlineCodeElement::Text(s) =>
-                                        format!("{}", replace_html_char_codes(s)),
-                                    InlineCodeElement::Em(s) =>
-                                        format!("<b>{}</b>", replace_html_char_codes(s)),
-                                })
-                                .collect::<String>()
-                        ),
-                    }
-                )
-            }
+            Element::H2(s) => format!("# {}\n\n", text_md(s).trim()),
+            Element::H3(s) => format!("## {}\n\n", text_md(s).trim()),
+            Element::Paragraph(vec_of_md) => format!("{}\n\n", to_md(vec_of_md).trim()),
+            Element::Text(s) => text_md(s),
+            Element::InlineCode(vice) => match vice.len() {
+                1 => match &vice[0] {
+                    InlineCodeElement::Text(s) => inline_code_md(s),
+                    InlineCodeElement::Em(s) => format!("**{}**", inline_code_md(s)),
+                },
+                _ => format!(
+                    "<code>{}</code>",
+                    vice.iter()
+                        .map(|m| match m {
+                            InlineCodeElement::Text(s) => html_text(s),
+                            InlineCodeElement::Em(s) => format!("<b>{}</b>", html_text(s)),
+                        })
+                        .collect::<String>()
+                ),
+            },
             // spans are used for easter eggs
-            Element::Span(title, text) => write!(f, "[{}](# \"{}\")", text, title),
-            Element::AnchorSpan(href, title, text) => {
-                write!(f, "[{}]({} \"{}\")", text, href, title)
+            Element::Span(title, text) => {
+                format!("[{}](# \"{}\")", text_md(text), title_md(title))
             }
-            Element::Em(vm) => write!(
-                f,
-                "**{}**",
-                replace_asterisks(&vm.into_iter().map(|m| m.to_string()).collect::<String>())
+            Element::AnchorSpan(href, title, text) => format!(
+                "[{}]({} \"{}\")",
+                text_md(text),
+                entities::decode(href),
+                title_md(title)
             ),
-            Element::EmStar(s) => write!(f, "***{}***", s),
-            Element::LinkRelative(href, text) => write!(f, "[{}]({})", text, convert_href(href)),
-            Element::LinkAbsolute(href, text) => write!(f, "[{}]({})", text, href),
-            Element::UnorderedList(vm) => write!(
-                f,
+            Element::Em(vm) => format!("**{}**", to_md(vm)),
+            Element::EmStar(s) => format!("***{}***", text_md(s)),
+            Element::LinkRelative(href, text) => {
+                let href = entities::decode(href);
+                format!("[{}]({})", text_md(text), convert_href(&href, puzzle))
+            }
+            Element::LinkAbsolute(href, text) => {
+                format!("[{}]({})", text_md(text), entities::decode(href))
+            }
+            // (markdown doesn't have superscript)
+            Element::Sup(vm) => format!("<sup>{}</sup>", to_md(vm)),
+            Element::Strikethrough(vm) => format!("~~{}~~", to_md(vm)),
+            Element::Italic(vm) => format!("*{}*", to_md(vm)),
+            Element::List(start, items) => format!("{}\n\n", list_md(*start, items, puzzle)),
+            Element::Blockquote(vm) => format!(
                 "{}\n\n",
-                vm.into_iter()
-                    .map(|li| format!("{}", li.to_string().trim()))
+                to_md(vm)
+                    .trim()
+                    .lines()
+                    .map(|l| format!("> {}", l).trim_end().to_string())
                     .collect::<Vec<String>>()
                     .join("\n")
-                    .trim()
-            ),
-            Element::ListItem(vm) => write!(
-                f,
-                "* {}",
-                vm.into_iter()
-                    .map(|m| match m {
-                        // only supports one level of nested list
-                        Element::UnorderedList(vm) => format!(
-                            "\n{}",
-                            vm.into_iter()
-                                .map(|li| format!("    {}", li.to_string().trim()))
-                                .collect::<Vec<String>>()
-                                .join("\n")
-                        ),
-                        _ => m.to_string(),
-                    })
-                    .collect::<String>()
             ),
+            Element::Table(rows) => format!("{}\n", table_md(rows, puzzle)),
             Element::CodeBlock(vcbe) => {
-                let mut has_html = false;
-                let contents = vcbe
-                    .into_iter()
-                    .map(|e| match e {
-                        CodeBlockElement::Em(s) => {
-                            has_html = true;
-                            format!("<b>{}</b>", s.to_string())
-                        }
-                        CodeBlockElement::Span(title, text) => {
-                            has_html = true;
-                            format!("<a href=\"#\" alt=\"{}\">{}</a>", title, text)
-                        }
-                        CodeBlockElement::Text(s) => s.to_string(),
-                    })
-                    .collect::<String>();
-
+                // (markdown can't do bold inside a code block, so that has to be HTML)
+                let has_html = vcbe.iter().any(|e| !matches!(e, CodeBlockElement::Text(_)));
                 if has_html {
-                    write!(
-                        f,
-                        "<pre><code>{}</code></pre>\n\n",
-                        replace_html_char_codes(&contents)
-                    )
+                    let contents = vcbe
+                        .iter()
+                        .map(|e| match e {
+                            CodeBlockElement::Em(s) => format!("<b>{}</b>", html_text(s)),
+                            CodeBlockElement::Span(title, text) => format!(
+                                "<a href=\"#\" alt=\"{}\">{}</a>",
+                                html_text(title),
+                                html_text(text)
+                            ),
+                            CodeBlockElement::Text(s) => html_text(s),
+                        })
+                        .collect::<String>();
+                    format!("<pre><code>{}</code></pre>\n\n", contents)
                 } else {
-                    write!(f, "```\n{}```\n\n", replace_html_char_codes(&contents))
+                    let contents = vcbe
+                        .iter()
+                        .map(|e| match e {
+                            CodeBlockElement::Text(s) => entities::decode(s),
+                            _ => String::new(),
+                        })
+                        .collect::<String>();
+                    format!("```\n{}```\n\n", contents)
                 }
             }
             // no double newline because this is the last thing printed
-            Element::ParagraphSuccess(s) => write!(f, "**{}**\n", replace_asterisks(s)),
-            Element::Form(vfe) => write!(
-                f,
+            Element::ParagraphSuccess(s) => {
+                format!("**{}**\n", replace_asterisks(&entities::decode(s)))
+            }
+            Element::Form(vfe) => format!(
                 "{}\n\n",
-                vfe.into_iter()
-                    .map(|m| m.to_string())
-                    .collect::<String>()
-                    .trim()
+                vfe.iter().map(|m| m.to_string()).collect::<String>().trim()
             ),
-            Element::Discard => write!(f, ""),
+            Element::RawHtml(s) => format!("{}\n\n", s.trim()),
+            Element::Discard => String::new(),
         }
     }
 }
 
+// one line per item, with nested lists indented under their item
+fn list_md(start: Option<u64>, items: &[Vec<Element>], puzzle: &Puzzle) -> String {
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, vm)| {
+            let marker = match start {
+                Some(n) => format!("{}.", n + i as u64),
+                None => String::from("*"),
+            };
+            let text = vm
+                .iter()
+                .filter(|m| !matches!(m, Element::List(_, _)))
+                .map(|m| m.to_md(puzzle))
+                .collect::<String>();
+            let nested = vm.iter().filter_map(|m| match m {
+                Element::List(start, items) => Some(list_md(*start, items, puzzle)),
+                _ => None,
+            });
+            let mut lines = vec![format!("{} {}", marker, text.trim())];
+            for list in nested {
+                lines.extend(list.lines().map(|l| format!("    {}", l)));
+            }
+            lines.join("\n")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// the first row is the header (markdown tables have to have one)
+fn table_md(rows: &[Vec<Vec<Element>>], puzzle: &Puzzle) -> String {
+    let num_columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let row_md = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let mut lines = rows.iter().map(|row| {
+        row_md(
+            (0..num_columns)
+                .map(|c| match row.get(c) {
+                    Some(vm) => vm
+                        .iter()
+                        .map(|m| m.to_md(puzzle))
+                        .collect::<String>()
+                        .trim()
+                        .replace('|', "\\|"),
+                    None => String::new(),
+                })
+                .collect(),
+        )
+    });
+    let mut table = lines.next().unwrap_or_default();
+    table.push_str(&row_md(vec![String::from("---"); num_columns]));
+    table.extend(lines);
+    table
+}
+
 impl fmt::Display for FormElement<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            FormElement::Text(s) => write!(f, "{}", normalize_whitespace(s)),
+            FormElement::Text(s) => write!(f, "{}", text_md(s)),
             FormElement::Input(s) => write!(f, "[{}](#)", s),
         }
     }
 }
 
-// table here: http://rabbit.eng.miami.edu/info/htmlchars.html
-fn replace_html_char_codes(s: &str) -> String {
-    s.replace("&lt;", "<")
-        .replace("&gt;", ">")
-        .replace("&quot;", "\"")
+// text in a paragraph, with the whitespace normalized first (so &nbsp; is kept)
+fn text_md(s: &str) -> String {
+    escape_md(&entities::decode(&normalize_whitespace(s)))
+}
+
+// characters that would otherwise be formatting (or HTML) in markdown
+fn escape_md(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        if "\\`*_[]<>&".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// (the code can have backticks in it, which need a longer delimiter)
+fn inline_code_md(s: &str) -> String {
+    let code = entities::decode(s);
+    if code.contains('`') {
+        format!("`` {} ``", code)
+    } else {
+        format!("`{}`", code)
+    }
+}
+
+// the title of a link, in double quotes
+fn title_md(s: &str) -> String {
+    entities::decode(s)
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+}
+
+// text that stays inside of HTML tags in the markdown
+// (decoded and re-encoded, so every reference is one that markdown renders)
+fn html_text(s: &str) -> String {
+    entities::decode(s)
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
 
 // so that asterisks inside of markdown bold ** don't mess things up
@@ -213,17 +311,44 @@ fn normalize_whitespace(s: &str) -> String {
     MATCH_WHITESPACE.replace_all(s, " ").into_owned()
 }
 
-fn convert_href(href: &str) -> String {
-    if let Ok((_, (year, day))) = match_day_url(href) {
-        format!("../../{}/descriptions/day{}.md", year, day)
-    } else if let Ok((_, day)) = one_or_two_digits(href) {
-        format!("./day{}.md", day)
+// the markdown file is in the 'descriptions' directory of the year
+fn convert_href(href: &str, puzzle: &Puzzle) -> String {
+    // (links to days can be to a section, like '/2022/day/3#part2')
+    let (path, fragment) = match href.find('#') {
+        Some(i) => href.split_at(i),
+        None => (href, ""),
+    };
+    if href.starts_with('#') || href.contains("://") || href.starts_with("mailto:") {
+        // (a section of the same page, or somewhere else entirely)
+        href.to_string()
+    } else if href.starts_with("//") {
+        // (somewhere else, with the same protocol)
+        format!("https:{}", href)
+    } else if let Ok((_, day)) = bare_day(path) {
+        format!("./day{}.md{}", day, fragment)
+    } else if let Ok((_, (year, day))) = match_day_url(path) {
+        // other years only link to the local file if it has been downloaded
+        if year == puzzle.year {
+            format!("./day{}.md{}", day, fragment)
+        } else if Path::new(&format!("../{}/descriptions/day{}.md", year, day)).exists() {
+            format!("../../{}/descriptions/day{}.md{}", year, day, fragment)
+        } else {
+            format!("{}{}", AOC_URL, href)
+        }
+    } else if href.starts_with('/') {
+        format!("{}{}", AOC_URL, href)
+    } else if href.starts_with('?') {
+        format!("{}/{}/day/{}{}", AOC_URL, puzzle.year, puzzle.day, href)
     } else {
-        println!("{}", href);
-        panic!("fuck");
+        // (relative to the description page)
+        format!("{}/{}/day/{}", AOC_URL, puzzle.year, href)
     }
 }
 
+fn bare_day(input: &str) -> IResult<&str, usize> {
+    terminated(one_or_two_digits, eof)(input)
+}
+
 fn match_day_url(input: &str) -> IResult<&str, (usize, usize)> {
     map(
         tuple((tag("/"), four_digits, tag("/day/"), one_or_two_digits, eof)),
@@ -244,50 +369,188 @@ fn one_or_two_digits(input: &str) -> IResult<&str, usize> {
     })(input)
 }
 
-pub(crate) fn html_to_md(html: &str) -> Result<String, String> {
-    let (leftover, markdown) = match parse_html_to_md(html) {
-        Ok((l, m)) => (l, m),
-        Err(err) => {
-            return Err(format!("Could not parse html: {}", err.to_string()));
+// how to handle markup that the parsers don't recognize
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseMode {
+    // fail, with where it happened
+    Strict,
+    // keep it as HTML, or strip it down to the text, with a warning for each
+    Lenient,
+}
+
+// the markdown, and any warnings about things that couldn't be converted
+pub fn html_to_md(
+    html: &str,
+    puzzle: &Puzzle,
+    mode: ParseMode,
+) -> Result<(String, Vec<String>), String> {
+    parse_with(html, mode, |elements, _| render(elements, puzzle))
+}
+
+// the title, parts, answers, etc. (and any warnings, same as for the markdown)
+pub fn parse_description(
+    html: &str,
+    puzzle: &Puzzle,
+    mode: ParseMode,
+) -> Result<(Description, Vec<String>), String> {
+    let (description, warnings) = parse_with(html, mode, |elements, position| {
+        Description::from_elements(elements, puzzle, position)
+    })?;
+    Ok((description?, warnings))
+}
+
+// the line and column in the original HTML, of a slice of the HTML that was parsed
+// (in lenient mode that is a copy, with some things stripped out)
+type Position<'p> = &'p dyn Fn(&str) -> (usize, usize);
+
+fn parse_with<T>(
+    html: &str,
+    mode: ParseMode,
+    f: impl FnOnce(&[Element], Position) -> T,
+) -> Result<(T, Vec<String>), String> {
+    match mode {
+        ParseMode::Strict => {
+            let elements = parse_html(html)?;
+            let position = |s: &str| line_column(html, &html[offset_in(html, s)..]);
+            Ok((f(&elements, &position), vec![]))
         }
+        ParseMode::Lenient => lenient::parse_with(html, f),
+    }
+}
+
+// (the slice has to be part of the html)
+fn offset_in(html: &str, s: &str) -> usize {
+    s.as_ptr() as usize - html.as_ptr() as usize
+}
+
+fn render(elements: &[Element], puzzle: &Puzzle) -> String {
+    elements.iter().map(|m| m.to_md(puzzle)).collect::<String>()
+}
+
+// the elements in <main>, or where (and why) that failed
+fn parse_html(html: &str) -> Result<Vec<Element>, String> {
+    let rest = main_start(html)?;
+    // (many1 can only fail if there are no elements at all, same as stopping there)
+    let (rest, elements) = match main_md(rest) {
+        Ok((r, e)) => (r, e),
+        Err(_) => (rest, vec![]),
     };
-    if leftover != "" {
-        return Err(format!("Parse incomplete, leftover='{}'", leftover));
+    match after_main(rest) {
+        Ok(_) if !elements.is_empty() => Ok(elements),
+        _ => Err(parse_failure(html, rest)),
     }
-    Ok(markdown)
 }
 
-fn parse_html_to_md(input: &str) -> IResult<&str, String> {
-    map(
-        tuple((before_main, main, after_main)),
-        |(_bm, main_md, _am)| main_md,
-    )(input)
+fn main_start(html: &str) -> Result<&str, String> {
+    match before_main(html) {
+        Ok((rest, _)) => Ok(rest),
+        Err(_) => Err(String::from("Could not parse html: there is no <main>")),
+    }
 }
 
 fn before_main(input: &str) -> IResult<&str, &str> {
     recognize(tuple((take_until1("<main>"), tag("<main>"))))(input)
 }
 
-fn main(input: &str) -> IResult<&str, String> {
-    map(main_md, |md: Vec<Element>| {
-        md.into_iter().map(|m| m.to_string()).collect::<String>()
-    })(input)
-}
-
 fn after_main(input: &str) -> IResult<&str, &str> {
     recognize(tuple((tag("</main>"), many1(anychar))))(input)
 }
 
+type ElementParser = fn(&str) -> IResult<&str, Element>;
+
+// everything that can be in <main>, in the order they are tried
+// (named for the error messages)
+const MAIN_PARSERS: &[(&str, ElementParser)] = &[
+    ("discard", discard),
+    ("header", header),
+    ("paragraph", paragraph),
+    ("list", list),
+    ("code_block", code_block),
+    ("blockquote", blockquote),
+    ("table", table),
+    ("paragraph_success", paragraph_success),
+    ("form", form),
+];
+
 fn main_md(input: &str) -> IResult<&str, Vec<Element>> {
-    many1(alt((
-        discard,
-        header,
-        paragraph,
-        ulist,
-        code_block,
-        paragraph_success,
-        form,
-    )))(input)
+    many1(main_element)(input)
+}
+
+fn main_element(input: &str) -> IResult<&str, Element> {
+    for (_, parser) in MAIN_PARSERS {
+        if let Ok(parsed) = parser(input) {
+            return Ok(parsed);
+        }
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        ErrorKind::Alt,
+    )))
+}
+
+// none of the parsers matched at the start of 'rest', so report the one that got the furthest
+// (which is usually the element with something new in it)
+fn parse_failure(html: &str, rest: &str) -> String {
+    let (name, failed_at) = furthest_failure(rest);
+    let (line, column) = line_column(html, rest);
+    let mut error = format!("Could not parse html at line {line}, column {column}");
+    if name.is_empty() {
+        error.push_str(": no element parser matched\n");
+        error.push_str(&snippet(html, rest));
+    } else {
+        let (line, column) = line_column(html, failed_at);
+        error.push_str(&format!(
+            ": {name} failed at line {line}, column {column}\n"
+        ));
+        error.push_str(&snippet(html, failed_at));
+    }
+    error
+}
+
+// the parser that got the furthest, and where it failed
+// (no name if they all failed at the start)
+fn furthest_failure(rest: &str) -> (&'static str, &str) {
+    MAIN_PARSERS
+        .iter()
+        .map(|(name, parser)| match parser(rest) {
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => (*name, e.input),
+            _ => (*name, rest),
+        })
+        .fold(("", rest), |furthest, (name, failed_at)| {
+            if failed_at.len() < furthest.1.len() {
+                (name, failed_at)
+            } else {
+                furthest
+            }
+        })
+}
+
+// (1-based, and 'at' has to be a suffix of 'html')
+fn line_column(html: &str, at: &str) -> (usize, usize) {
+    let before = &html[..html.len() - at.len()];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
+
+// part of the line around a position, with a marker under it
+fn snippet(html: &str, at: &str) -> String {
+    const WIDTH: usize = 40;
+    let before = &html[..html.len() - at.len()];
+    let line_before = before.rsplit('\n').next().unwrap_or("");
+    let line_after = at.split('\n').next().unwrap_or("");
+    let num_before = line_before.chars().count();
+    let before: String = line_before
+        .chars()
+        .skip(num_before.saturating_sub(WIDTH))
+        .collect();
+    let after: String = line_after.chars().take(WIDTH).collect();
+    format!(
+        "  {}{}\n  {}^",
+        before,
+        after,
+        " ".repeat(before.chars().count())
+    )
 }
 
 // things which will be discarded
@@ -351,16 +614,29 @@ fn sharing(input: &str) -> IResult<&str, &str> {
 }
 
 // headers
-// (currently only h2)
 fn header(input: &str) -> IResult<&str, Element> {
-    map(
-        delimited(
-            alt((tag("<h2>"), tag("<h2 id=\"part2\">"))),
-            take_until1("</h2>"),
-            tag("</h2>"),
+    alt((
+        map(
+            delimited(open_tag("h2"), take_until1("</h2>"), tag("</h2>")),
+            |text| Element::H2(text),
         ),
-        |text| Element::H2(text),
-    )(input)
+        map(
+            delimited(open_tag("h3"), take_until1("</h3>"), tag("</h3>")),
+            Element::H3,
+        ),
+    ))(input)
+}
+
+// like '<td>' or '<td class="something">'
+fn open_tag<'a>(name: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    recognize(tuple((
+        tag("<"),
+        tag(name),
+        alt((
+            tag(">"),
+            recognize(tuple((multispace1, take_until(">"), tag(">")))),
+        )),
+    )))
 }
 
 // paragraphs
@@ -376,9 +652,13 @@ fn paragraph(input: &str) -> IResult<&str, Element> {
     )(input)
 }
 fn paragraph_contents(input: &str) -> IResult<&str, Vec<Element>> {
-    many1(alt((
-        p_a_span, p_code, p_span, p_em, p_em_star, p_a, p_text,
-    )))(input)
+    many1(inline)(input)
+}
+// anything that can be in a paragraph
+fn inline(input: &str) -> IResult<&str, Element> {
+    alt((
+        p_a_span, p_code, p_span, p_em, p_em_star, p_a, p_sup, p_s, p_i, p_text,
+    ))(input)
 }
 fn p_a_span(input: &str) -> IResult<&str, Element> {
     map(
@@ -428,15 +708,10 @@ fn p_span(input: &str) -> IResult<&str, Element> {
     )(input)
 }
 fn p_em(input: &str) -> IResult<&str, Element> {
-    // <em> can contain <code> and <span> in paragraphs
-    map(
-        delimited(
-            tag("<em>"),
-            many1(alt((p_text, p_code, p_span))),
-            tag("</em>"),
-        ),
-        |vm| Element::Em(vm),
-    )(input)
+    // <em> can contain <code> and <span> (and links, etc.) in paragraphs
+    map(delimited(tag("<em>"), many1(inline), tag("</em>")), |vm| {
+        Element::Em(vm)
+    })(input)
 }
 fn p_em_star(input: &str) -> IResult<&str, Element> {
     map(
@@ -475,30 +750,107 @@ fn p_a_absolute(input: &str) -> IResult<&str, Element> {
         |(href, text)| Element::LinkAbsolute(href, text),
     )(input)
 }
+fn p_sup(input: &str) -> IResult<&str, Element> {
+    map(
+        delimited(tag("<sup>"), many1(inline), tag("</sup>")),
+        Element::Sup,
+    )(input)
+}
+fn p_s(input: &str) -> IResult<&str, Element> {
+    map(delimited(tag("<s>"), many1(inline), tag("</s>")), |vm| {
+        Element::Strikethrough(vm)
+    })(input)
+}
+fn p_i(input: &str) -> IResult<&str, Element> {
+    map(delimited(tag("<i>"), many1(inline), tag("</i>")), |vm| {
+        Element::Italic(vm)
+    })(input)
+}
 fn p_text(input: &str) -> IResult<&str, Element> {
     map(take_until1("<"), |text| Element::Text(text))(input)
 }
 
-fn ulist(input: &str) -> IResult<&str, Element> {
+// lists can be nested in list items, to any depth
+fn list(input: &str) -> IResult<&str, Element> {
+    alt((
+        map(
+            delimited(open_tag("ul"), many1(list_item), tag("</ul>")),
+            |items| Element::List(None, items),
+        ),
+        map(
+            tuple((open_tag("ol"), many1(list_item), tag("</ol>"))),
+            |(open, items, _)| Element::List(Some(list_start(open)), items),
+        ),
+    ))(input)
+}
+fn list_item(input: &str) -> IResult<&str, Vec<Element>> {
+    delimited(
+        whitespace_opt,
+        delimited(open_tag("li"), many1(alt((list, inline))), tag("</li>")),
+        whitespace_opt,
+    )(input)
+}
+// like '<ol start="5">' (otherwise ordered lists start at 1)
+fn list_start(open: &str) -> u64 {
+    let parsed: IResult<&str, u64> =
+        preceded(tuple((take_until("start=\""), tag("start=\""))), nom_u64)(open);
+    parsed.map_or(1, |(_, n)| n)
+}
+
+fn blockquote(input: &str) -> IResult<&str, Element> {
     map(
-        delimited(tag("<ul>"), many1(list_item), tag("</ul>")),
-        |vm| Element::UnorderedList(vm),
+        delimited(
+            tag("<blockquote>"),
+            many1(alt((
+                discard_whitespace,
+                paragraph,
+                list,
+                code_block,
+                map(many1(inline), Element::Paragraph),
+            ))),
+            tag("</blockquote>"),
+        ),
+        Element::Blockquote,
     )(input)
 }
-fn list_item(input: &str) -> IResult<&str, Element> {
+fn discard_whitespace(input: &str) -> IResult<&str, Element> {
+    map(whitespace, |_| Element::Discard)(input)
+}
+
+// (the header cells are treated the same as the others)
+fn table(input: &str) -> IResult<&str, Element> {
     map(
-        tuple((
-            whitespace_opt,
-            delimited(
-                tag("<li>"),
-                many1(alt((ulist, p_code, p_span, p_em, p_em_star, p_a, p_text))),
-                tag("</li>"),
-            ),
-            whitespace_opt,
-        )),
-        |(_, vm, _)| Element::ListItem(vm),
+        delimited(
+            open_tag("table"),
+            many1(delimited(table_ignore, table_row, table_ignore)),
+            tag("</table>"),
+        ),
+        Element::Table,
     )(input)
 }
+fn table_row(input: &str) -> IResult<&str, Vec<Vec<Element>>> {
+    delimited(
+        open_tag("tr"),
+        many1(delimited(whitespace_opt, table_cell, whitespace_opt)),
+        tag("</tr>"),
+    )(input)
+}
+fn table_cell(input: &str) -> IResult<&str, Vec<Element>> {
+    alt((
+        delimited(open_tag("th"), many0(inline), tag("</th>")),
+        delimited(open_tag("td"), many0(inline), tag("</td>")),
+    ))(input)
+}
+// whitespace, and the tags that group rows
+fn table_ignore(input: &str) -> IResult<&str, Vec<&str>> {
+    many0(alt((
+        whitespace,
+        open_tag("thead"),
+        tag("</thead>"),
+        open_tag("tbody"),
+        tag("</tbody>"),
+    )))(input)
+}
 
 fn code_block(input: &str) -> IResult<&str, Element> {
     // there can be <em> and <span> inside these
@@ -0,0 +1,196 @@
+use super::{
+    entities, normalize_whitespace, CodeBlockElement, Element, InlineCodeElement, Position,
+};
+use crate::cli::Part;
+use crate::puzzle::Puzzle;
+
+// everything on a description page, for the commands that need more than the markdown
+#[derive(Debug)]
+pub struct Description {
+    // like "Day 1: Calorie Counting"
+    pub title: String,
+    pub part1: Section,
+    // (once part 1 is solved)
+    pub part2: Option<Section>,
+}
+
+// one part of the puzzle, from its heading up to the next one
+#[derive(Debug)]
+pub struct Section {
+    // like "--- Part Two ---"
+    pub heading: String,
+    pub markdown: String,
+    pub code_blocks: Vec<CodeBlock>,
+    // the titles that show up when hovering over some things
+    pub easter_eggs: Vec<String>,
+    // from "Your puzzle answer was ..." (once this part is solved)
+    pub answer: Option<String>,
+}
+
+// the text of a <pre><code> block, without the markup
+#[derive(Debug)]
+pub struct CodeBlock {
+    pub text: String,
+    // the line and column where the text starts in the HTML
+    // (an empty block has no text, so it has no position)
+    pub position: Option<(usize, usize)>,
+}
+
+impl Description {
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.answer.as_deref(),
+            Part::Two => self.part2.as_ref()?.answer.as_deref(),
+        }
+    }
+
+    // each heading starts a section
+    // (anything before the first heading goes with part 1)
+    pub(super) fn from_elements(
+        elements: &[Element],
+        puzzle: &Puzzle,
+        position: Position,
+    ) -> Result<Self, String> {
+        let mut sections: Vec<Vec<&Element>> = vec![vec![]];
+        for element in elements.iter().filter(|e| !matches!(e, Element::Discard)) {
+            let has_heading = sections
+                .last()
+                .is_some_and(|s| s.iter().any(|e| is_heading(e)));
+            if is_heading(element) && has_heading {
+                sections.push(vec![]);
+            }
+            if let Some(section) = sections.last_mut() {
+                section.push(element);
+            }
+        }
+
+        let mut sections = sections
+            .into_iter()
+            .map(|s| Section::from_elements(&s, puzzle, position));
+        let part1 = sections
+            .next()
+            .filter(|s| !s.heading.is_empty())
+            .ok_or(String::from("the description has no heading"))?;
+        let title = part1
+            .heading
+            .trim_matches(|c: char| c == '-' || c.is_whitespace())
+            .to_string();
+        let part2 = sections.next();
+        if let Some(extra) = sections.next() {
+            return Err(format!(
+                "the description has a section after part 2: '{}'",
+                extra.heading
+            ));
+        }
+        Ok(Description {
+            title,
+            part1,
+            part2,
+        })
+    }
+}
+
+impl Section {
+    fn from_elements(elements: &[&Element], puzzle: &Puzzle, position: Position) -> Self {
+        let heading = elements
+            .iter()
+            .find_map(|e| match e {
+                Element::H2(s) => Some(text(s)),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let markdown = elements.iter().map(|e| e.to_md(puzzle)).collect();
+        let answer = elements.iter().find_map(|e| answer(e));
+
+        let mut code_blocks = vec![];
+        let mut easter_eggs = vec![];
+        for element in elements {
+            walk(element, &mut |e| match e {
+                Element::CodeBlock(vcbe) => {
+                    let start = vcbe.first().map(|cbe| match cbe {
+                        CodeBlockElement::Em(s)
+                        | CodeBlockElement::Span(_, s)
+                        | CodeBlockElement::Text(s) => position(s),
+                    });
+                    let text = vcbe
+                        .iter()
+                        .map(|cbe| match cbe {
+                            CodeBlockElement::Em(s)
+                            | CodeBlockElement::Span(_, s)
+                            | CodeBlockElement::Text(s) => entities::decode(s),
+                        })
+                        .collect();
+                    code_blocks.push(CodeBlock {
+                        text,
+                        position: start,
+                    });
+                    easter_eggs.extend(vcbe.iter().filter_map(|cbe| match cbe {
+                        CodeBlockElement::Span(title, _) => Some(entities::decode(title)),
+                        _ => None,
+                    }));
+                }
+                Element::Span(title, _) | Element::AnchorSpan(_, title, _) => {
+                    easter_eggs.push(entities::decode(title));
+                }
+                _ => (),
+            });
+        }
+
+        Section {
+            heading,
+            markdown,
+            code_blocks,
+            easter_eggs,
+            answer,
+        }
+    }
+}
+
+fn is_heading(element: &Element) -> bool {
+    matches!(element, Element::H2(_))
+}
+
+fn text(s: &str) -> String {
+    entities::decode(&normalize_whitespace(s))
+        .trim()
+        .to_string()
+}
+
+// <p>Your puzzle answer was <code>24000</code>.</p>
+fn answer(element: &Element) -> Option<String> {
+    match element {
+        Element::Paragraph(vm) => match (vm.first(), vm.get(1)) {
+            (Some(Element::Text(t)), Some(Element::InlineCode(vice)))
+                if text(t) == "Your puzzle answer was" =>
+            {
+                Some(
+                    vice.iter()
+                        .map(|ice| match ice {
+                            InlineCodeElement::Text(s) | InlineCodeElement::Em(s) => {
+                                entities::decode(s)
+                            }
+                        })
+                        .collect(),
+                )
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// this element, and everything inside of it
+fn walk<'e, 'a>(element: &'e Element<'a>, f: &mut impl FnMut(&'e Element<'a>)) {
+    f(element);
+    match element {
+        Element::Paragraph(vm)
+        | Element::Em(vm)
+        | Element::Sup(vm)
+        | Element::Strikethrough(vm)
+        | Element::Italic(vm)
+        | Element::Blockquote(vm) => vm.iter().for_each(|e| walk(e, f)),
+        Element::List(_, items) => items.iter().flatten().for_each(|e| walk(e, f)),
+        Element::Table(rows) => rows.iter().flatten().flatten().for_each(|e| walk(e, f)),
+        _ => (),
+    }
+}
@@ -0,0 +1,75 @@
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::alphanumeric1;
+use nom::character::complete::digit1;
+use nom::character::complete::hex_digit1;
+use nom::character::complete::one_of;
+use nom::combinator::map;
+use nom::combinator::map_opt;
+use nom::sequence::delimited;
+use nom::sequence::preceded;
+use nom::IResult;
+
+mod names;
+
+// decode the character references in some HTML text, like
+// &lt;  &#39;  &#x2192;
+// (anything that isn't a valid reference is left as-is)
+pub(crate) fn decode(s: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        decoded.push_str(&rest[..i]);
+        rest = &rest[i..];
+        match entity(rest) {
+            Ok((after, c)) => {
+                decoded.push_str(&c);
+                rest = after;
+            }
+            Err(_) => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn entity(input: &str) -> IResult<&str, String> {
+    delimited(
+        tag("&"),
+        alt((map(numeric, String::from), map(named, String::from))),
+        tag(";"),
+    )(input)
+}
+
+fn numeric(input: &str) -> IResult<&str, char> {
+    preceded(
+        tag("#"),
+        alt((
+            preceded(one_of("xX"), map(hex_digit1, |h| code_point(h, 16))),
+            map(digit1, |d| code_point(d, 10)),
+        )),
+    )(input)
+}
+
+// (zero, surrogates, and anything too big aren't characters, so those are the replacement character)
+fn code_point(digits: &str, radix: u32) -> char {
+    u32::from_str_radix(digits, radix)
+        .ok()
+        .filter(|n| *n != 0)
+        .and_then(char::from_u32)
+        .unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+fn named(input: &str) -> IResult<&str, &'static str> {
+    map_opt(alphanumeric1, named_entity)(input)
+}
+
+fn named_entity(name: &str) -> Option<&'static str> {
+    names::NAMED
+        .binary_search_by_key(&name, |(n, _)| n)
+        .ok()
+        .map(|i| names::NAMED[i].1)
+}
@@ -0,0 +1,246 @@
+use std::ops::Range;
+
+use nom::bytes::complete::tag;
+use nom::bytes::complete::take_until;
+use nom::character::complete::alphanumeric1;
+use nom::combinator::opt;
+use nom::sequence::tuple;
+use nom::IResult;
+
+use super::{furthest_failure, line_column, main_md, main_start, offset_in, Element, Position};
+
+// the tags that the parsers know about (in some places, anyway)
+const KNOWN_TAGS: &[&str] = &[
+    "a",
+    "article",
+    "blockquote",
+    "code",
+    "em",
+    "form",
+    "h2",
+    "h3",
+    "i",
+    "input",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "s",
+    "span",
+    "style",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+// tags that are never closed
+const VOID_TAGS: &[&str] = &["br", "hr", "img", "input", "link", "meta", "wbr"];
+
+// like '<b>', '<span class="x">', or '</b>'
+struct Tag<'a> {
+    name: &'a str,
+    closing: bool,
+    self_closing: bool,
+    len: usize,
+}
+
+// what to take out of the HTML, so that the parsers can get past it
+struct Strip {
+    open: Range<usize>,
+    close: Option<Range<usize>>,
+    parser: &'static str,
+}
+
+// unrecognized elements inside of something the parsers know are stripped down to their text,
+// and anything else is kept as HTML
+pub(super) fn parse_with<T>(
+    original: &str,
+    f: impl FnOnce(&[Element], Position) -> T,
+) -> Result<(T, Vec<String>), String> {
+    let mut html = original.to_string();
+    let main = html.len() - main_start(&html)?.len();
+    let mut warnings = vec![];
+    // (so the warnings can have positions in the original)
+    let mut removed: Vec<Range<usize>> = vec![];
+    let position = |removed: &[Range<usize>], offset: usize| {
+        let offset = removed
+            .iter()
+            .rev()
+            .fold(offset, |o, r| if o >= r.start { o + r.len() } else { o });
+        line_column(original, &original[offset..])
+    };
+    loop {
+        match parse(&html, main) {
+            Ok((elements, kept)) => {
+                for (offset, raw) in kept {
+                    let (line, column) = position(&removed, offset);
+                    warnings.push(format!(
+                        "kept {raw} as HTML at line {line}, column {column}"
+                    ));
+                }
+                let in_original = |s: &str| position(&removed, offset_in(&html, s));
+                return Ok((f(&elements, &in_original), warnings));
+            }
+            Err(strip) => {
+                let (line, column) = position(&removed, strip.open.start);
+                warnings.push(format!(
+                    "stripped {} in {} at line {line}, column {column}",
+                    &html[strip.open.clone()],
+                    strip.parser
+                ));
+                // (the close is after the open, so take that out first)
+                if let Some(close) = strip.close {
+                    html.replace_range(close.clone(), "");
+                    removed.push(close);
+                }
+                html.replace_range(strip.open.clone(), "");
+                removed.push(strip.open);
+            }
+        }
+    }
+}
+
+// parse everything in <main>, or find the next thing to strip out
+// what was kept as HTML, and where
+type Kept = Vec<(usize, String)>;
+
+fn parse(html: &str, main: usize) -> Result<(Vec<Element<'_>>, Kept), Strip> {
+    let mut rest = &html[main..];
+    let mut elements = vec![];
+    let mut kept = vec![];
+    loop {
+        if let Ok((r, mut parsed)) = main_md(rest) {
+            elements.append(&mut parsed);
+            rest = r;
+        }
+        if rest.is_empty() || rest.starts_with("</main>") {
+            return Ok((elements, kept));
+        }
+        let (name, failed_at) = furthest_failure(rest);
+        if failed_at.len() < rest.len() {
+            if let Some(strip) = strip_at(html, name, failed_at) {
+                return Err(strip);
+            }
+        }
+        // (none of the parsers could get started on this)
+        let raw = whole_element(rest);
+        kept.push((html.len() - rest.len(), describe(raw)));
+        elements.push(Element::RawHtml(raw));
+        rest = &rest[raw.len()..];
+    }
+}
+
+// the parser failed at a tag that it doesn't know, or at a known one with an unknown one inside
+// (known tags are never stripped, so anything else is kept as HTML instead)
+fn strip_at(html: &str, parser: &'static str, failed_at: &str) -> Option<Strip> {
+    let failed_tag = tag_at(failed_at)?;
+    let start = if !KNOWN_TAGS.contains(&failed_tag.name) {
+        0
+    } else if failed_tag.closing {
+        return None;
+    } else {
+        let inside = &failed_at[failed_tag.len..];
+        let end = closing_tag(inside, failed_tag.name).map_or(inside.len(), |c| c.start);
+        failed_tag.len + unknown_tag(&inside[..end])?
+    };
+    let at = &failed_at[start..];
+    let t = tag_at(at)?;
+    let offset = html.len() - at.len();
+    let close = if t.closing || t.self_closing {
+        None
+    } else {
+        closing_tag(&at[t.len..], t.name)
+            .map(|c| (offset + t.len + c.start)..(offset + t.len + c.end))
+    };
+    Some(Strip {
+        open: offset..(offset + t.len),
+        close,
+        parser,
+    })
+}
+
+// where the first tag that the parsers don't know is
+fn unknown_tag(input: &str) -> Option<usize> {
+    input.match_indices('<').map(|(i, _)| i).find(|i| {
+        tag_at(&input[*i..])
+            .map(|t| !KNOWN_TAGS.contains(&t.name))
+            .unwrap_or(false)
+    })
+}
+
+fn tag_at(input: &str) -> Option<Tag<'_>> {
+    let parsed: IResult<&str, _> = tuple((
+        tag("<"),
+        opt(tag("/")),
+        alphanumeric1,
+        take_until(">"),
+        tag(">"),
+    ))(input);
+    let (rest, (_, slash, name, attributes, _)) = parsed.ok()?;
+    if !(attributes.is_empty() || attributes.starts_with([' ', '\n', '\t', '/'])) {
+        return None;
+    }
+    Some(Tag {
+        name,
+        closing: slash.is_some(),
+        self_closing: attributes.ends_with('/') || VOID_TAGS.contains(&name),
+        len: input.len() - rest.len(),
+    })
+}
+
+// where the tag is closed (after the opening tag), allowing for the same tag nested inside
+fn closing_tag(input: &str, name: &str) -> Option<Range<usize>> {
+    let mut depth = 1;
+    let mut pos = 0;
+    while let Some(i) = input[pos..].find('<') {
+        let at = pos + i;
+        match tag_at(&input[at..]) {
+            Some(t) if t.name == name && !t.self_closing => {
+                if t.closing {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(at..(at + t.len));
+                    }
+                } else {
+                    depth += 1;
+                }
+                pos = at + t.len;
+            }
+            Some(t) => pos = at + t.len,
+            None => pos = at + 1,
+        }
+    }
+    None
+}
+
+// the element at the start of the input, through its closing tag
+// (or just the tag, or the text up to the next tag)
+fn whole_element(input: &str) -> &str {
+    match tag_at(input) {
+        Some(t) if !t.closing && !t.self_closing => match closing_tag(&input[t.len..], t.name) {
+            Some(c) => &input[..(t.len + c.end)],
+            None => &input[..t.len],
+        },
+        Some(t) => &input[..t.len],
+        None => {
+            let end = input
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| *c == '<')
+                .map_or(input.len(), |(i, _)| i);
+            &input[..end]
+        }
+    }
+}
+
+fn describe(raw: &str) -> String {
+    match tag_at(raw) {
+        Some(t) => String::from(&raw[..t.len]),
+        None => format!("text '{}'", raw.trim()),
+    }
+}
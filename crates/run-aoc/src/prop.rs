@@ -0,0 +1,194 @@
+use std::env;
+use std::fmt::Debug;
+use std::fs;
+use std::panic;
+
+use utils::rng::Rng;
+
+use crate::cli::input_file_path;
+
+const DEFAULT_CASES: usize = 200;
+const DEFAULT_SEED: u64 = 2022;
+// give up shrinking after this many successful steps
+const MAX_SHRINK_STEPS: usize = 1000;
+
+// how a day generates random (valid) inputs, and makes them smaller
+pub struct Generator {
+    pub generate: fn(&mut Rng) -> String,
+    // candidates that are (hopefully) smaller than the input, tried in order
+    pub shrink: fn(&str) -> Vec<String>,
+}
+
+// run the solution and a slow brute-force reference on a bunch of generated inputs,
+// and panic if they ever disagree (or the solution panics)
+//
+// the reference is also the validator: if it panics on a shrunk candidate,
+// that candidate is not a valid input and is skipped
+//
+// the number of cases and the seed can be changed with AOC_PROP_CASES and AOC_PROP_SEED
+pub fn check_against_reference<T>(
+    day: usize,
+    gen: &Generator,
+    solution: fn(String) -> T,
+    reference: fn(String) -> T,
+) where
+    T: Debug + PartialEq,
+{
+    let num_cases = env_or("AOC_PROP_CASES", DEFAULT_CASES as u64) as usize;
+    let seed = env_or("AOC_PROP_SEED", DEFAULT_SEED);
+
+    for case in 0..num_cases {
+        // each case gets its own seed, so it can be reproduced on its own
+        let mut rng = Rng::new(seed.wrapping_add(case as u64));
+        let input = (gen.generate)(&mut rng);
+        if let Some(failure) = check_one(&input, solution, reference) {
+            let (input, failure) = shrink(input, failure, gen, solution, reference);
+            let file = input_file_path(day, "counterexample");
+            let saved = match fs::write(&file, &input) {
+                Ok(_) => format!("saved to '{}'", file),
+                Err(err) => format!("failed to save to '{}': {}", file, err),
+            };
+            panic!(
+                "day {} failed for case {} (seed {}): {}\n({})\ninput:\n{}",
+                day, case, seed, failure, saved, input
+            );
+        }
+    }
+}
+
+fn env_or(var: &str, default: u64) -> u64 {
+    match env::var(var) {
+        Ok(s) => s
+            .parse()
+            .unwrap_or_else(|_| panic!("could not parse {}='{}' as a number", var, s)),
+        Err(_) => default,
+    }
+}
+
+// returns a description of the failure, or None if the input is ok (or invalid)
+fn check_one<T>(
+    input: &str,
+    solution: fn(String) -> T,
+    reference: fn(String) -> T,
+) -> Option<String>
+where
+    T: Debug + PartialEq,
+{
+    let expected = match panic::catch_unwind(|| reference(input.to_string())) {
+        Ok(e) => e,
+        // invalid input
+        Err(_) => return None,
+    };
+    match panic::catch_unwind(|| solution(input.to_string())) {
+        Ok(actual) if actual == expected => None,
+        Ok(actual) => Some(format!("expected {:?}, found {:?}", expected, actual)),
+        Err(err) => Some(format!("solution panicked: {}", panic_message(&err))),
+    }
+}
+
+fn panic_message(err: &Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = err.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = err.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("(unknown panic)")
+    }
+}
+
+// greedily take the first smaller candidate that still fails, until none of them do
+fn shrink<T>(
+    input: String,
+    failure: String,
+    gen: &Generator,
+    solution: fn(String) -> T,
+    reference: fn(String) -> T,
+) -> (String, String)
+where
+    T: Debug + PartialEq,
+{
+    let mut input = input;
+    let mut failure = failure;
+    for _ in 0..MAX_SHRINK_STEPS {
+        let smaller = (gen.shrink)(&input)
+            .into_iter()
+            .filter(|c| c.len() < input.len())
+            .find_map(|c| check_one(&c, solution, reference).map(|f| (c, f)));
+        match smaller {
+            Some((c, f)) => {
+                input = c;
+                failure = f;
+            }
+            None => break,
+        }
+    }
+    (input, failure)
+}
+
+// shrinkers for common input shapes
+
+// no shrinking
+pub fn shrink_none(_input: &str) -> Vec<String> {
+    vec![]
+}
+
+// remove one line at a time
+pub fn shrink_lines(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    (0..lines.len())
+        .map(|skip| {
+            join_lines(
+                lines
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != skip)
+                    .map(|(_, l)| *l),
+            )
+        })
+        .collect()
+}
+
+// remove one group at a time (groups are separated by blank lines)
+pub fn shrink_groups(input: &str) -> Vec<String> {
+    let groups: Vec<&str> = input.trim_end().split("\n\n").collect();
+    (0..groups.len())
+        .map(|skip| {
+            let kept: Vec<&str> = groups
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != skip)
+                .map(|(_, g)| *g)
+                .collect();
+            format!("{}\n", kept.join("\n\n"))
+        })
+        .collect()
+}
+
+// remove one row or one column at a time from a grid of characters
+pub fn shrink_grid(input: &str) -> Vec<String> {
+    let rows: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+    let num_cols = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let mut candidates = shrink_lines(input);
+    for skip in 0..num_cols {
+        candidates.push(join_lines(rows.iter().map(|r| {
+            r.iter()
+                .enumerate()
+                .filter(|(c, _)| *c != skip)
+                .map(|(_, ch)| *ch)
+                .collect::<String>()
+        })));
+    }
+    candidates
+}
+
+fn join_lines<I, S>(lines: I) -> String
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    lines.fold(String::new(), |mut acc, l| {
+        acc.push_str(l.as_ref());
+        acc.push('\n');
+        acc
+    })
+}
@@ -0,0 +1,352 @@
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ureq::Agent;
+
+use crate::cli::input_file_path;
+use crate::download::{
+    agent_for_dl, base_url, check_description, check_input, conditional_get, url_to_buf,
+    BadDownload, DLOpt, Validators,
+};
+use crate::encrypt::encrypted_path;
+use crate::history::{days_from_civil, format_timestamp};
+use crate::parse::{html_to_md, ParseMode};
+
+// puzzles unlock at midnight US-Eastern (UTC-5) on December 1-25
+const UNLOCK_HOUR_UTC: u64 = 5;
+const LAST_DAY: usize = 25;
+
+// one day's puzzle, which knows where its files are (locally, and on the server)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Puzzle {
+    pub year: usize,
+    pub day: usize,
+}
+
+impl Puzzle {
+    pub fn new(year: usize, day: usize) -> Self {
+        Puzzle { year, day }
+    }
+
+    // description URL example:
+    // https://adventofcode.com/2022/day/15
+    pub fn description_url(&self) -> String {
+        format!("{}/{}/day/{}", base_url(), self.year, self.day)
+    }
+
+    // https://adventofcode.com/2022/day/15/input
+    pub fn input_url(&self) -> String {
+        format!("{}/input", self.description_url())
+    }
+
+    // https://adventofcode.com/2022/day/15/answer
+    pub fn answer_url(&self) -> String {
+        format!("{}/answer", self.description_url())
+    }
+
+    // local files are relative to the year directory
+    pub fn html_path(&self) -> String {
+        format!("descriptions/day{}.html", self.day)
+    }
+
+    pub fn md_path(&self) -> String {
+        format!("descriptions/day{}.md", self.day)
+    }
+
+    pub fn input_path(&self) -> String {
+        input_file_path(self.day, "input")
+    }
+
+    // seconds since the epoch
+    pub fn unlock_time(&self) -> u64 {
+        let days = days_from_civil(self.year as i64, 12, self.day as u32);
+        days as u64 * 86_400 + UNLOCK_HOUR_UTC * 3600
+    }
+
+    // None if it's already unlocked
+    pub fn time_until_unlock(&self, now: u64) -> Option<Duration> {
+        let unlock = self.unlock_time();
+        if now < unlock {
+            Some(Duration::from_secs(unlock - now))
+        } else {
+            None
+        }
+    }
+
+    // (downloading before the unlock is just a 404 from the server)
+    pub fn check_unlocked(&self) -> Result<(), String> {
+        if self.day == 0 || self.day > LAST_DAY {
+            return Err(format!(
+                "there is no puzzle for day {} (only days 1-{LAST_DAY})",
+                self.day
+            ));
+        }
+        match self.time_until_unlock(now_secs()) {
+            Some(wait) => Err(format!(
+                "day {} unlocks in {} (at {} UTC)",
+                self.day,
+                format_hms(wait),
+                format_timestamp(self.unlock_time())
+            )),
+            None => Ok(()),
+        }
+    }
+
+    // sleep until the puzzle unlocks, showing the countdown now and then
+    pub fn wait_for_unlock(&self) -> Result<(), String> {
+        if self.day == 0 || self.day > LAST_DAY {
+            return self.check_unlocked();
+        }
+        while let Some(wait) = self.time_until_unlock(now_secs()) {
+            println!("day {} unlocks in {}", self.day, format_hms(wait));
+            // (wait a bit after the unlock, in case the clocks are a little different)
+            let next = wait.min(Duration::from_secs(60));
+            thread::sleep(next + Duration::from_secs(1));
+        }
+        Ok(())
+    }
+
+    pub fn dl_html(&self, dlo: DLOpt) -> Result<(), String> {
+        let file_loc_html = self.html_path();
+        if !should_download(&file_loc_html, "HTML", dlo) {
+            return Ok(());
+        }
+        let url = self.description_url();
+        self.check_unlocked()?;
+        println!("{url} --> {file_loc_html}");
+        let agent = agent_for_dl()?;
+        // if it's already saved, the server can say it hasn't changed
+        let validators = if Path::new(&file_loc_html).exists() {
+            Validators::load(&file_loc_html)
+        } else {
+            Validators::default()
+        };
+        let (bytes, validators) = match conditional_get(&url, &agent, &validators)? {
+            Some(r) => r,
+            None => {
+                println!("(HTML not modified since the last download)");
+                return Ok(());
+            }
+        };
+        if let Err(bad) = check_description(&bytes) {
+            return Err(format!("not saving '{file_loc_html}': {bad}"));
+        }
+        write_file(&file_loc_html, bytes)?;
+        validators.save(&file_loc_html)
+    }
+
+    // first download the HTML file if it doesn't exist, then parse that to markdown
+    pub fn dl_md(&self, dlo: DLOpt) -> Result<(), String> {
+        // TODO: eventually want to skip writing the HTMl file and go straight to md
+        // (but for testing this is better, to avoid hitting the server every time)
+        self.dl_html(dlo)?;
+
+        let html_contents = fs::read_to_string(self.html_path())
+            .expect("could not read the file, I know it exists!!!");
+        let (md_contents, warnings) = html_to_md(&html_contents, self, ParseMode::Lenient)?;
+        for warning in warnings {
+            println!("warning: {warning}");
+        }
+        write_file(&self.md_path(), md_contents)
+    }
+
+    // for the 'md' command, which also downloads the HTML again if it doesn't have part 2 yet
+    // (part 2 shows up after part 1 is solved)
+    pub fn update_md(&self, dlo: DLOpt) -> Result<(), String> {
+        if dlo == DLOpt::IfNoExist && missing_part2(&self.html_path()) {
+            println!("(HTML doesn't have part 2 yet, downloading again)");
+            return self.dl_md(DLOpt::Force);
+        }
+        self.dl_md(dlo)
+    }
+
+    // download the input
+    // (unlike auto_download, this fails if the session cookie is not setup)
+    pub fn dl_input(&self, dlo: DLOpt) -> Result<(), String> {
+        let file_loc = self.input_path();
+        if !should_download(&file_loc, "input", dlo) {
+            return Ok(());
+        }
+        self.check_unlocked()?;
+        let agent = agent_for_dl()?;
+        download(&self.input_url(), &file_loc, &agent, check_input)
+    }
+
+    // auto-download the input, if it's not already there
+    // (because this is auto, don't fail if session cookie is not setup)
+    pub fn auto_download(&self) -> Result<(), String> {
+        let file_loc = self.input_path();
+        // (the runner can use the encrypted one)
+        if Path::new(&encrypted_path(&file_loc)).exists() {
+            return Ok(());
+        }
+        if !should_download(&file_loc, "input", DLOpt::IfNoExist) {
+            return Ok(());
+        }
+        self.check_unlocked()?;
+        let agent = match agent_for_dl() {
+            Ok(a) => a,
+            Err(err) => {
+                println!("({err}, skipping auto-download)");
+                return Ok(());
+            }
+        };
+        download(&self.input_url(), &file_loc, &agent, check_input)
+    }
+}
+
+// what happened to one file in fetch_all
+#[derive(Debug, Eq, PartialEq)]
+pub enum Fetched {
+    Fetched,
+    Skipped(String),
+    Failed(String),
+}
+
+type DlFn = fn(&Puzzle) -> Result<(), String>;
+
+// stop trying after this many failures in a row (like when the session is expired)
+const MAX_FAILURES_IN_A_ROW: usize = 3;
+
+// download the descriptions (as markdown) and inputs for a range of days
+// (files that already exist are skipped, so this can be run again to resume)
+pub fn fetch_all(year: usize, from: usize, to: usize) -> Vec<(usize, &'static str, Fetched)> {
+    let mut results = vec![];
+    let mut failures_in_a_row = 0;
+    for day in from..=to {
+        let puzzle = Puzzle::new(year, day);
+        let items: [(&str, String, DlFn); 2] = [
+            ("description", puzzle.md_path(), |p| {
+                p.dl_md(DLOpt::IfNoExist)
+            }),
+            ("input", puzzle.input_path(), |p| {
+                p.dl_input(DLOpt::IfNoExist)
+            }),
+        ];
+        for (what, file_loc, dl_fn) in items {
+            let result = if Path::new(&file_loc).exists()
+                || Path::new(&encrypted_path(&file_loc)).exists()
+            {
+                Fetched::Skipped(String::from("already exists"))
+            } else if failures_in_a_row >= MAX_FAILURES_IN_A_ROW {
+                Fetched::Skipped(format!(
+                    "stopped after {MAX_FAILURES_IN_A_ROW} failures in a row"
+                ))
+            } else if let Err(err) = puzzle.check_unlocked() {
+                Fetched::Skipped(err)
+            } else {
+                println!("day {day} {what}:");
+                match dl_fn(&puzzle) {
+                    Ok(_) => {
+                        failures_in_a_row = 0;
+                        Fetched::Fetched
+                    }
+                    Err(err) => {
+                        failures_in_a_row += 1;
+                        Fetched::Failed(err)
+                    }
+                }
+            };
+            results.push((day, what, result));
+        }
+    }
+    results
+}
+
+pub fn print_fetch_report(results: &[(usize, &str, Fetched)]) {
+    println!("\n{:>3}  {:<11}  result", "day", "file");
+    for (day, what, result) in results {
+        let result = match result {
+            Fetched::Fetched => String::from("fetched"),
+            Fetched::Skipped(why) => format!("skipped ({why})"),
+            // (only the first line, errors can include the whole response)
+            Fetched::Failed(err) => format!("FAILED: {}", err.lines().next().unwrap_or("")),
+        };
+        println!("{:>3}  {:<11}  {}", day, what, result);
+    }
+    let count = |f: fn(&Fetched) -> bool| results.iter().filter(|(_, _, r)| f(r)).count();
+    println!(
+        "\n{} fetched, {} skipped, {} failed",
+        count(|r| matches!(r, Fetched::Fetched)),
+        count(|r| matches!(r, Fetched::Skipped(_))),
+        count(|r| matches!(r, Fetched::Failed(_))),
+    );
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// like 05:43:21 (or 3d 05:43:21)
+pub fn format_hms(d: Duration) -> String {
+    let secs = d.as_secs();
+    let hms = format!(
+        "{:02}:{:02}:{:02}",
+        (secs % 86_400) / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    );
+    if secs >= 86_400 {
+        format!("{}d {}", secs / 86_400, hms)
+    } else {
+        hms
+    }
+}
+
+// files are cached, and only downloaded again when forced
+fn should_download(file_loc: &str, what: &str, dlo: DLOpt) -> bool {
+    if let Ok(true) = Path::new(file_loc).try_exists() {
+        if dlo == DLOpt::Force {
+            println!("({what} already exists, but forcing download)");
+        } else {
+            println!("({what} already exists, skipping download - use --force to overwrite)");
+            return false;
+        }
+    }
+    true
+}
+
+// an existing description that was saved before part 1 was solved
+fn missing_part2(file_loc_html: &str) -> bool {
+    match fs::read_to_string(file_loc_html) {
+        Ok(html) => !html.contains("<h2 id=\"part2\">"),
+        Err(_) => false,
+    }
+}
+
+// the response is checked before it's saved, so a bad download doesn't replace a good file
+fn download(
+    url: &str,
+    file_loc: &str,
+    agent: &Agent,
+    check: fn(&[u8]) -> Result<(), BadDownload>,
+) -> Result<(), String> {
+    let bytes = url_to_buf(url, agent)?;
+    if let Err(bad) = check(&bytes) {
+        return Err(format!("not saving '{file_loc}': {bad}"));
+    }
+    write_file(file_loc, bytes)
+}
+
+// write to a temp file and rename it, so there's never a partial file
+fn write_file<C: AsRef<[u8]>>(file_loc: &str, contents: C) -> Result<(), String> {
+    let temp_file = format!("{file_loc}.tmp");
+    let result = fs::write(&temp_file, contents).and_then(|_| fs::rename(&temp_file, file_loc));
+    match result {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            let _ = fs::remove_file(&temp_file);
+            let err_str = if let Some(inner_err) = err.into_inner() {
+                format!("{inner_err}")
+            } else {
+                String::from("Some std::io::Error happened")
+            };
+            Err(format!("Failed to write file: {err_str}"))
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::fs;
+use std::path::Path;
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::bytes::complete::take_until;
+use nom::combinator::map;
+use nom::combinator::opt;
+use nom::combinator::value;
+use nom::multi::many0;
+use nom::sequence::preceded;
+use nom::sequence::terminated;
+use nom::sequence::tuple;
+use nom::IResult;
+use utils::nom_usize;
+
+use crate::download::{agent_for_dl, base_url, url_to_buf};
+use crate::encrypt::encrypted_path;
+use crate::puzzle::Puzzle;
+
+// stars for each day on the calendar page, and the local files for that day
+pub struct DayStatus {
+    pub day: usize,
+    // (None if the day is not unlocked yet)
+    pub stars: Option<usize>,
+    pub has_html: bool,
+    pub has_md: bool,
+    pub has_input: bool,
+    pub part1_tests: usize,
+    pub part2_tests: usize,
+}
+
+pub fn show(year: usize, all_years: bool) -> Result<(), String> {
+    print!("{}", render(year, all_years)?);
+    Ok(())
+}
+
+// the calendar, the local files, and the stars for the other years
+// (the URLs are printed as they are fetched)
+pub fn render(year: usize, all_years: bool) -> Result<String, String> {
+    let agent = agent_for_dl()?;
+    let url = format!("{}/{year}", base_url());
+    println!("{url}");
+    let bytes = url_to_buf(&url, &agent)?;
+    let calendar = parse_calendar(&String::from_utf8_lossy(&bytes))?;
+
+    let days: Vec<DayStatus> = (1..=25)
+        .map(|day| local_status(year, day, &calendar))
+        .collect();
+    let mut output = format!("{}\n{}", calendar_grid(year, &days), local_table(&days));
+
+    if all_years {
+        let url = format!("{}/events", base_url());
+        println!("{url}");
+        let bytes = url_to_buf(&url, &agent)?;
+        let events = parse_events(&String::from_utf8_lossy(&bytes))?;
+        output.push('\n');
+        for (y, stars) in events {
+            output.push_str(&format!("{y}: {stars:>2}*\n"));
+        }
+    }
+    Ok(output)
+}
+
+fn local_status(year: usize, day: usize, calendar: &[(usize, usize)]) -> DayStatus {
+    let puzzle = Puzzle::new(year, day);
+    let source = fs::read_to_string(format!("src/day{day}.rs")).unwrap_or_default();
+    DayStatus {
+        day,
+        stars: calendar.iter().find(|(d, _)| *d == day).map(|(_, s)| *s),
+        has_html: Path::new(&puzzle.html_path()).exists(),
+        has_md: Path::new(&puzzle.md_path()).exists(),
+        has_input: Path::new(&puzzle.input_path()).exists()
+            || Path::new(&encrypted_path(&puzzle.input_path())).exists(),
+        part1_tests: count_tests(&source, day, "part1"),
+        part2_tests: count_tests(&source, day, "part2"),
+    }
+}
+
+// the day module has the tests (like 'test_fn!(day1, part1, input, 123)')
+// (the arguments can be on separate lines, and slow tests are like 'part1_SLOW')
+pub fn count_tests(source: &str, day: usize, part: &str) -> usize {
+    let day = format!("day{day}");
+    source
+        .match_indices("test_fn!(")
+        .filter(|(i, _)| {
+            // (not commented out)
+            let line_start = source[..*i].rfind('\n').map_or(0, |n| n + 1);
+            !source[line_start..*i].contains("//")
+        })
+        .filter(|(i, m)| {
+            let mut args = source[(i + m.len())..].split(',').map(|a| a.trim());
+            args.next() == Some(day.as_str())
+                && args
+                    .next()
+                    .is_some_and(|p| p == part || p.starts_with(&format!("{part}_")))
+        })
+        .count()
+}
+
+// 5 rows of 5 days, like
+//  1 **   2 **   3 *    4 .    5
+pub fn calendar_grid(year: usize, days: &[DayStatus]) -> String {
+    let total: usize = days.iter().filter_map(|d| d.stars).sum();
+    let mut out = format!("{year}: {total} stars\n\n");
+    for row in days.chunks(5) {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|d| {
+                let stars = match d.stars {
+                    Some(0) => ".",
+                    Some(1) => "*",
+                    Some(_) => "**",
+                    None => "",
+                };
+                format!("{:>2} {:<2}", d.day, stars)
+            })
+            .collect();
+        out.push_str(cells.join("   ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+pub fn local_table(days: &[DayStatus]) -> String {
+    let mut out = format!(
+        "{:>3}  {:<5}  {:<11}  {:<5}  {:<11}  notes\n",
+        "day", "stars", "description", "input", "tests (1/2)"
+    );
+    for d in days {
+        let stars = match d.stars {
+            Some(0) => String::from("."),
+            Some(n) => "*".repeat(n),
+            None => String::from("-"),
+        };
+        let description = match (d.has_html, d.has_md) {
+            (true, true) => "html, md",
+            (true, false) => "html",
+            (false, true) => "md",
+            (false, false) => "-",
+        };
+        let input = if d.has_input { "yes" } else { "-" };
+        let mut notes = vec![];
+        if d.stars.unwrap_or(0) > 0 && !d.has_input {
+            notes.push("no input");
+        }
+        if d.stars.unwrap_or(0) > 0 && d.part1_tests == 0 {
+            notes.push("no part 1 tests");
+        }
+        if d.stars.unwrap_or(0) > 1 && d.part2_tests == 0 {
+            notes.push("no part 2 tests");
+        }
+        let line = format!(
+            "{:>3}  {:<5}  {:<11}  {:<5}  {:<11}  {}",
+            d.day,
+            stars,
+            description,
+            input,
+            format!("{}/{}", d.part1_tests, d.part2_tests),
+            notes.join(", ")
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+// the calendar has a link for each unlocked day, like
+// <a aria-label="Day 3, two stars" href="/2022/day/3" class="calendar-day3 calendar-verycomplete">
+pub fn parse_calendar(html: &str) -> Result<Vec<(usize, usize)>, String> {
+    match many0(preceded(take_until("aria-label=\"Day "), day_label))(html) {
+        Ok((_, days)) if !days.is_empty() => Ok(days),
+        _ => Err(String::from("could not find any days in the calendar")),
+    }
+}
+
+fn day_label(input: &str) -> IResult<&str, (usize, usize)> {
+    map(
+        tuple((
+            tag("aria-label=\"Day "),
+            nom_usize,
+            opt(alt((
+                value(1, tag(", one star")),
+                value(2, tag(", two stars")),
+            ))),
+            tag("\""),
+        )),
+        |(_, day, stars, _)| (day, stars.unwrap_or(0)),
+    )(input)
+}
+
+// the events page lists every year, like
+// <div class="eventlist-event"><a href="/2022">[2022]</a> <span class="star-count">50*</span></div>
+pub fn parse_events(html: &str) -> Result<Vec<(usize, usize)>, String> {
+    match many0(preceded(
+        take_until("<div class=\"eventlist-event\">"),
+        event,
+    ))(html)
+    {
+        Ok((_, events)) if !events.is_empty() => Ok(events),
+        _ => Err(String::from("could not find any events")),
+    }
+}
+
+fn event(input: &str) -> IResult<&str, (usize, usize)> {
+    map(
+        tuple((
+            tag("<div class=\"eventlist-event\"><a href=\""),
+            take_until(">["),
+            tag(">["),
+            nom_usize,
+            tag("]</a>"),
+            opt(preceded(
+                terminated(take_until("<"), tag("<span class=\"star-count\">")),
+                terminated(nom_usize, tag("*")),
+            )),
+        )),
+        |(_, _, _, year, _, stars)| (year, stars.unwrap_or(0)),
+    )(input)
+}
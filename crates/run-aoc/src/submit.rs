@@ -0,0 +1,229 @@
+use std::fmt;
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use nom::bytes::complete::tag;
+use nom::bytes::complete::take_until;
+use nom::combinator::map;
+use nom::combinator::opt;
+use nom::sequence::terminated;
+use nom::sequence::tuple;
+use nom::IResult;
+use regex::Regex;
+use utils::nom_u64;
+
+use crate::cli::Part;
+use crate::download::{agent_for_dl, governor, DLOpt};
+use crate::puzzle::Puzzle;
+
+lazy_static! {
+    static ref MATCH_TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+}
+
+// what the server said about a submitted answer
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    AlreadySolved,
+    RateLimited(Duration),
+    Unknown(String),
+}
+
+impl Verdict {
+    // for saving in the guess ledger
+    pub(crate) fn key(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::AlreadySolved => "already-solved",
+            Verdict::RateLimited(_) => "rate-limited",
+            Verdict::Unknown(_) => "unknown",
+        }
+    }
+
+    // (the wait time and the unknown text are not saved)
+    pub(crate) fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "correct" => Some(Verdict::Correct),
+            "incorrect" => Some(Verdict::Incorrect),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "already-solved" => Some(Verdict::AlreadySolved),
+            "rate-limited" => Some(Verdict::RateLimited(Duration::ZERO)),
+            "unknown" => Some(Verdict::Unknown(String::new())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct!"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::TooHigh => write!(f, "incorrect (too high)"),
+            Verdict::TooLow => write!(f, "incorrect (too low)"),
+            Verdict::AlreadySolved => write!(f, "already solved (or that part is not unlocked)"),
+            Verdict::RateLimited(wait) => {
+                write!(
+                    f,
+                    "rate limited, wait {}s before trying again",
+                    wait.as_secs()
+                )
+            }
+            Verdict::Unknown(text) => write!(f, "unknown response: {}", text),
+        }
+    }
+}
+
+pub fn submit(puzzle: Puzzle, part: Part, answer: &str) -> Result<Verdict, String> {
+    let day = puzzle.day;
+    let answer = answer_to_submit(answer)?;
+    // don't waste a submission (and the timeout) on an answer that's known to be wrong
+    crate::ledger::check(day, part, &answer)?;
+    let level = match part {
+        Part::One => "1",
+        Part::Two => "2",
+    };
+    let url = puzzle.answer_url();
+    println!("submitting '{answer}' for day {day}, part {part} --> {url}");
+
+    let agent = agent_for_dl()?;
+    governor::throttle("POST", &url)?;
+    let html = match agent
+        .post(&url)
+        .send_form(&[("level", level), ("answer", &answer)])
+    {
+        Ok(r) => r
+            .into_string()
+            .or(Err(String::from("Failed to read response")))?,
+        Err(ureq::Error::Status(code, response)) => {
+            return Err(format!(
+                "Request failed: {code}, {}",
+                String::from(response.status_text()),
+            ));
+        }
+        Err(ureq::Error::Transport(t)) => {
+            return Err(t.to_string());
+        }
+    };
+
+    let verdict = parse_response(&html);
+    println!("{verdict}");
+    if let Err(err) = crate::ledger::record(day, part, &answer, verdict.clone()) {
+        println!("(failed to record the guess: {err})");
+    }
+
+    // the description changes after a correct answer (part 2 shows up, and the answers are shown)
+    if verdict == Verdict::Correct {
+        println!("refreshing the description...");
+        puzzle.dl_md(DLOpt::Force)?;
+    }
+    Ok(verdict)
+}
+
+// multi-line answers are block letters, so submit the letters instead
+pub(crate) fn answer_to_submit(answer: &str) -> Result<String, String> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return Err(String::from("answer is empty"));
+    }
+    if answer.contains('\n') {
+        return utils::ocr::ocr_str(answer)
+            .map_err(|e| format!("could not read letters from the multi-line answer: {e}"));
+    }
+    Ok(answer.to_string())
+}
+
+// the result is the text of the <article> in the response
+pub fn parse_response(html: &str) -> Verdict {
+    let text = match article_text(html) {
+        Ok((_, text)) => MATCH_TAG.replace_all(text, "").trim().to_string(),
+        Err(_) => {
+            return Verdict::Unknown(String::from("(no <article> in the response)"));
+        }
+    };
+
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else if text.contains("You gave an answer too recently") {
+        match wait_time(&text) {
+            Some(wait) => Verdict::RateLimited(wait),
+            None => Verdict::Unknown(text),
+        }
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        }
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+fn article_text(input: &str) -> IResult<&str, &str> {
+    map(
+        tuple((
+            take_until("<article>"),
+            tag("<article>"),
+            take_until("</article>"),
+        )),
+        |(_, _, text)| text,
+    )(input)
+}
+
+// like "You have 37s left to wait", or "You have 4m 12s left to wait"
+fn wait_time(text: &str) -> Option<Duration> {
+    let (_, (_, minutes, seconds)) = tuple((
+        terminated(take_until("You have "), tag("You have ")),
+        opt(terminated(nom_u64, tag("m "))),
+        opt(terminated(nom_u64, tag("s left to wait"))),
+    ))(text)
+    .ok()?;
+    if minutes.is_none() && seconds.is_none() {
+        return None;
+    }
+    Some(Duration::from_secs(
+        minutes.unwrap_or(0) * 60 + seconds.unwrap_or(0),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wait_times() {
+        let wait = |text| wait_time(text).map(|d| d.as_secs());
+        assert_eq!(wait("You have 4m 12s left to wait."), Some(252));
+        assert_eq!(wait("You have 37s left to wait."), Some(37));
+        assert_eq!(wait("You have 2m left to wait."), Some(120));
+        assert_eq!(wait("Please wait a bit."), None);
+    }
+
+    #[test]
+    fn answers_to_submit() {
+        assert_eq!(answer_to_submit("  1234\n"), Ok(String::from("1234")));
+        assert!(answer_to_submit(" \n").is_err());
+        let letters = "###...##..
+#..#.#..#.
+#..#.#....
+###..#....
+#....#..#.
+#.....##..";
+        assert_eq!(answer_to_submit(letters), Ok(String::from("PC")));
+        assert!(answer_to_submit("#.#\n.#.")
+            .unwrap_err()
+            .starts_with("could not read letters"));
+    }
+}
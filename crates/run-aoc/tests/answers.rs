@@ -0,0 +1,136 @@
+// import the answers from the downloaded descriptions
+
+use std::fs;
+
+use run_aoc::answers::{as_submitted, expected, import, read};
+use run_aoc::cli::Part;
+
+mod common;
+use common::{setup, LOCK};
+
+const DAY1: &str = "<!DOCTYPE html>
+<html lang=\"en-us\">
+<body>
+<main>
+<article class=\"day-desc\"><h2>--- Day 1: Calorie Counting ---</h2><p>Count.</p></article>
+<p>Your puzzle answer was <code>24000</code>.</p><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Again.</p></article>
+<p>Your puzzle answer was <code>45000</code>.</p>
+</main>
+</body>
+</html>
+";
+
+const DAY2: &str = "<!DOCTYPE html>
+<html lang=\"en-us\">
+<body>
+<main>
+<article class=\"day-desc\"><h2>--- Day 2: Rock Paper Scissors ---</h2><p>Play.</p></article>
+<p>Your puzzle answer was <code>15</code>.</p><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Again.</p></article>
+</main>
+</body>
+</html>
+";
+
+#[test]
+fn import_all_days() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = setup("answers-all", "http://127.0.0.1:1");
+    fs::write(dir.join("descriptions/day1.html"), DAY1).unwrap();
+    fs::write(dir.join("descriptions/day2.html"), DAY2).unwrap();
+    fs::write(
+        dir.join("answers.txt"),
+        "2\tone\tinput\t12\n1\tone\texample\t7\n",
+    )
+    .unwrap();
+
+    import(2022, None).expect("import failed");
+    let expected_file = "1\tone\texample\t7
+1\tone\tinput\t24000
+1\ttwo\tinput\t45000
+2\tone\tinput\t15
+";
+    assert_eq!(
+        fs::read_to_string(dir.join("answers.txt")).unwrap(),
+        expected_file
+    );
+    assert_eq!(read().unwrap().len(), 4);
+    assert_eq!(expected(1, Part::Two, "input"), Ok(String::from("45000")));
+    assert_eq!(expected(1, Part::One, "example"), Ok(String::from("7")));
+    assert!(expected(2, Part::Two, "input")
+        .unwrap_err()
+        .contains("answers import 2"));
+
+    // (nothing changes the second time)
+    import(2022, None).expect("import failed");
+    assert_eq!(
+        fs::read_to_string(dir.join("answers.txt")).unwrap(),
+        expected_file
+    );
+}
+
+#[test]
+fn import_with_bad_days() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = setup("answers-bad-days", "http://127.0.0.1:1");
+    fs::write(dir.join("descriptions/day1.html"), DAY1).unwrap();
+    // (can't be parsed, and can't be read)
+    fs::write(dir.join("descriptions/day2.html"), "<html>no main</html>").unwrap();
+    fs::create_dir(dir.join("descriptions/day3.html")).unwrap();
+
+    import(2022, None).expect("import failed");
+    assert_eq!(
+        fs::read_to_string(dir.join("answers.txt")).unwrap(),
+        "1\tone\tinput\t24000\n1\ttwo\tinput\t45000\n"
+    );
+    assert!(import(2022, Some(2)).unwrap_err().starts_with("day 2: "));
+    assert!(import(2022, Some(3))
+        .unwrap_err()
+        .starts_with("Failed to read 'descriptions/day3.html'"));
+}
+
+#[test]
+fn import_one_day() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = setup("answers-one", "http://127.0.0.1:1");
+    fs::write(dir.join("descriptions/day2.html"), DAY2).unwrap();
+
+    import(2022, Some(2)).expect("import failed");
+    assert_eq!(
+        fs::read_to_string(dir.join("answers.txt")).unwrap(),
+        "2\tone\tinput\t15\n"
+    );
+
+    let err = import(2022, Some(3)).unwrap_err();
+    assert!(err.contains("html 3"), "{}", err);
+}
+
+#[test]
+fn bad_line() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = setup("answers-bad", "http://127.0.0.1:1");
+    fs::write(
+        dir.join("answers.txt"),
+        "1\tone\tinput\t5\n1\tthree\tinput\n",
+    )
+    .unwrap();
+    assert_eq!(
+        read().unwrap_err(),
+        "answers.txt line 2: expected 4 fields in answer line, found 3"
+    );
+}
+
+#[test]
+fn block_letters() {
+    let output = "
+###...##..
+#..#.#..#.
+#..#.#....
+###..#....
+#....#..#.
+#.....##..
+";
+    assert_eq!(as_submitted(output), "PC");
+    assert_eq!(as_submitted("24000"), "24000");
+    // (output that isn't letters is left alone)
+    assert_eq!(as_submitted("#.#\n.#."), "#.#\n.#.");
+}
@@ -0,0 +1,66 @@
+// check the session cookie against a local stand-in for the AoC server
+
+use std::collections::HashMap;
+use std::env;
+
+use run_aoc::auth;
+
+mod common;
+use common::{setup, start_server, LOCK};
+
+const LOGGED_IN: &str = "<!DOCTYPE html>
+<html lang=\"en-us\">
+<body>
+<header><div><h1 class=\"title-global\"><a href=\"/\">Advent of Code</a></h1><div class=\"user\">Test User <span class=\"star-count\">50*</span></div></div></header>
+<main>
+<article><p>These are your settings.</p></article>
+</main>
+</body>
+</html>
+";
+
+const LOGGED_OUT: &str = "<!DOCTYPE html>
+<html lang=\"en-us\">
+<body>
+<header><div><h1 class=\"title-global\"><a href=\"/\">Advent of Code</a></h1></div></header>
+<main>
+<p>To play, please identify yourself via one of these services:</p>
+</main>
+</body>
+</html>
+";
+
+#[test]
+fn check_logged_in() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) = start_server(HashMap::from([("/settings", LOGGED_IN)]));
+    setup("auth", &base_url);
+
+    auth::check().expect("should be logged in");
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0].0, "GET /settings HTTP/1.1");
+    assert_eq!(requests[0].1.get("cookie").unwrap(), "session=test-session");
+}
+
+#[test]
+fn check_logged_out() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, _requests) = start_server(HashMap::from([("/settings", LOGGED_OUT)]));
+    setup("auth-logged-out", &base_url);
+
+    let err = auth::check().unwrap_err();
+    assert!(err.contains("not logged in"), "unexpected error: {}", err);
+}
+
+#[test]
+fn check_env_var_cookie() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) = start_server(HashMap::from([("/settings", LOGGED_IN)]));
+    setup("auth-env", &base_url);
+    env::set_var("AOC_SESSION", "env-session");
+
+    auth::check().expect("should be logged in");
+    env::remove_var("AOC_SESSION");
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0].1.get("cookie").unwrap(), "session=env-session");
+}
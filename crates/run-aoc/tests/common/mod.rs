@@ -0,0 +1,127 @@
+// a local stand-in for the AoC server, with canned pages
+// (no network access needed)
+
+// each test file uses different parts of this
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// the requests that the server received (request line, and the headers)
+pub type RequestLog = Arc<Mutex<Vec<(String, HashMap<String, String>)>>>;
+
+// a canned response
+pub struct Page {
+    pub status: u16,
+    pub headers: Vec<(&'static str, &'static str)>,
+    pub body: Vec<u8>,
+}
+
+impl Page {
+    pub fn new(status: u16, body: &str) -> Self {
+        Page {
+            status,
+            headers: vec![],
+            body: body.as_bytes().to_vec(),
+        }
+    }
+}
+
+// serve the canned pages on a random port, and return the base URL
+pub fn start_server(pages: HashMap<&'static str, &'static str>) -> (String, RequestLog) {
+    start_server_with_status(pages.into_iter().map(|(p, b)| (p, (200, b))).collect())
+}
+
+// (for pages that have a different status, like errors)
+pub fn start_server_with_status(
+    pages: HashMap<&'static str, (u16, &'static str)>,
+) -> (String, RequestLog) {
+    start_server_with_pages(
+        pages
+            .into_iter()
+            .map(|(p, (status, body))| (p, Page::new(status, body)))
+            .collect(),
+    )
+}
+
+// (for pages with headers)
+// if a page has an ETag, requests with a matching If-None-Match get a 304
+pub fn start_server_with_pages(pages: HashMap<&'static str, Page>) -> (String, RequestLog) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("could not bind");
+    let addr = listener.local_addr().unwrap();
+    let requests: RequestLog = Arc::new(Mutex::new(vec![]));
+    let log = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut headers = HashMap::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+                }
+            }
+            let path = request_line.split(' ').nth(1).unwrap_or("").to_string();
+            let if_none_match = headers.get("if-none-match").cloned();
+            log.lock()
+                .unwrap()
+                .push((request_line.trim().to_string(), headers));
+
+            let not_found = Page::new(404, "404 Not Found");
+            let page = pages.get(path.as_str()).unwrap_or(&not_found);
+            let etag = page
+                .headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case("etag"))
+                .map(|(_, v)| v.to_string());
+            let (status, body): (u16, &[u8]) = match (&etag, &if_none_match) {
+                (Some(e), Some(inm)) if e == inm => (304, &[]),
+                _ => (page.status, &page.body),
+            };
+            let mut response = format!(
+                "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n",
+                status,
+                body.len()
+            );
+            for (name, value) in page.headers.iter() {
+                response.push_str(&format!("{}: {}\r\n", name, value));
+            }
+            response.push_str("\r\n");
+            stream.write_all(response.as_bytes()).unwrap();
+            stream.write_all(body).unwrap();
+        }
+    });
+    (format!("http://{}", addr), requests)
+}
+
+// the downloads use the current directory and the home directory, which are global,
+// so only one test can run at a time
+pub static LOCK: Mutex<()> = Mutex::new(());
+
+// point everything at a temp dir (for the year), and the local server
+pub fn setup(name: &str, base_url: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("run-aoc-test-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("descriptions")).unwrap();
+    fs::create_dir_all(dir.join("inputs")).unwrap();
+    fs::write(dir.join(".aoc-session-cookie"), "test-session\n").unwrap();
+    env::set_current_dir(&dir).unwrap();
+    env::set_var("HOME", &dir);
+    env::set_var("AOC_BASE_URL", base_url);
+    env::set_var("AOC_REQUEST_INTERVAL", "0");
+    env::remove_var("AOC_SESSION");
+    dir
+}
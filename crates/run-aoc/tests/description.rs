@@ -0,0 +1,106 @@
+// the parts of a description page
+
+use run_aoc::cli::Part;
+use run_aoc::parse::{parse_description, ParseMode};
+use run_aoc::puzzle::Puzzle;
+
+const BOTH_PARTS: &str = "<!DOCTYPE html>
+<html lang=\"en-us\">
+<head><title>Day 1 - Advent of Code 2022</title></head>
+<body>
+<main>
+<article class=\"day-desc\"><h2>--- Day 1: Calorie Counting ---</h2><p>The <span title=\"Elves &amp; snacks\">Elves</span> count.</p>
+<pre><code>1000
+<em>2000</em>
+</code></pre>
+</article>
+<p>Your puzzle answer was <code>24000</code>.</p><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Now the <a href=\"https://example.com\" target=\"_blank\"><span title=\"top three\">top</span></a> three.</p>
+<pre><code>a &lt; b
+</code></pre>
+<pre><code>c
+</code></pre>
+</article>
+<p>Your puzzle answer was <code>45000</code>.</p><p class=\"day-success\">Both parts of this puzzle are complete! They provide two gold stars: **</p>
+</main>
+</body>
+</html>
+";
+
+const PART1: &str = "<!DOCTYPE html>
+<html lang=\"en-us\">
+<body>
+<main>
+<article class=\"day-desc\"><h2>--- Day 2: Rock Paper Scissors ---</h2><p>Play.</p></article>
+</main>
+</body>
+</html>
+";
+
+#[test]
+fn both_parts() {
+    let (description, warnings) =
+        parse_description(BOTH_PARTS, &Puzzle::new(2022, 1), ParseMode::Strict)
+            .expect("parse failed");
+    assert!(warnings.is_empty());
+    assert_eq!(description.title, "Day 1: Calorie Counting");
+
+    let part1 = &description.part1;
+    assert_eq!(part1.heading, "--- Day 1: Calorie Counting ---");
+    assert!(part1.markdown.starts_with(
+        "# --- Day 1: Calorie Counting ---\n\nThe [Elves](# \"Elves & snacks\") count.\n\n"
+    ));
+    assert_eq!(part1.code_blocks.len(), 1);
+    assert_eq!(part1.code_blocks[0].text, "1000\n2000\n");
+    assert_eq!(part1.code_blocks[0].position, Some((7, 12)));
+    assert_eq!(part1.easter_eggs, vec!["Elves & snacks"]);
+    assert_eq!(part1.answer.as_deref(), Some("24000"));
+
+    let part2 = description.part2.as_ref().expect("no part 2");
+    assert_eq!(part2.heading, "--- Part Two ---");
+    assert_eq!(part2.code_blocks.len(), 2);
+    assert_eq!(part2.code_blocks[0].text, "a < b\n");
+    assert_eq!(part2.code_blocks[1].position, Some((14, 12)));
+    assert_eq!(part2.easter_eggs, vec!["top three"]);
+    assert_eq!(description.answer(Part::One), Some("24000"));
+    assert_eq!(description.answer(Part::Two), Some("45000"));
+}
+
+#[test]
+fn part1_only() {
+    let (description, _) =
+        parse_description(PART1, &Puzzle::new(2022, 2), ParseMode::Strict).expect("parse failed");
+    assert_eq!(description.title, "Day 2: Rock Paper Scissors");
+    assert!(description.part1.code_blocks.is_empty());
+    assert_eq!(description.part1.answer, None);
+    assert!(description.part2.is_none());
+    assert_eq!(description.answer(Part::One), None);
+    assert_eq!(description.answer(Part::Two), None);
+}
+
+#[test]
+fn positions_in_lenient_mode() {
+    // (the <b> tags are stripped before the code block is parsed)
+    let html = PART1.replace(
+        "<p>Play.</p>",
+        "<p><b>Play</b>.</p>\n<pre><code>x <b>y</b>\n</code></pre>",
+    );
+    let (description, warnings) =
+        parse_description(&html, &Puzzle::new(2022, 2), ParseMode::Lenient).expect("parse failed");
+    assert_eq!(warnings.len(), 2);
+    let code_block = &description.part1.code_blocks[0];
+    assert_eq!(code_block.text, "x y\n");
+    assert_eq!(code_block.position, Some((6, 12)));
+}
+
+#[test]
+fn extra_section() {
+    let html = BOTH_PARTS.replace(
+        "<p class=\"day-success\">",
+        "<article class=\"day-desc\"><h2>--- Part Three ---</h2><p>More?</p></article><p class=\"day-success\">",
+    );
+    let err = parse_description(&html, &Puzzle::new(2022, 1), ParseMode::Strict).unwrap_err();
+    assert_eq!(
+        err,
+        "the description has a section after part 2: '--- Part Three ---'"
+    );
+}
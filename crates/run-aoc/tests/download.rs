@@ -0,0 +1,417 @@
+// run the downloads against a local stand-in for the AoC server
+
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+use run_aoc::download::DLOpt;
+use run_aoc::puzzle::{fetch_all, format_hms, Fetched, Puzzle};
+
+mod common;
+use common::{setup, start_server, start_server_with_pages, start_server_with_status, Page, LOCK};
+
+const DESCRIPTION: &str = "<!DOCTYPE html>
+<html lang=\"en-us\">
+<head><title>Day 1 - Advent of Code 2022</title></head>
+<body>
+<main>
+<article class=\"day-desc\"><h2>--- Day 1: Testing ---</h2><p>The elves are <em>testing</em> the downloader.</p></article>
+</main>
+</body>
+</html>
+";
+
+const DESCRIPTION_PART2: &str = "<!DOCTYPE html>
+<html lang=\"en-us\">
+<head><title>Day 2 - Advent of Code 2022</title></head>
+<body>
+<main>
+<article class=\"day-desc\"><h2>--- Day 2: Testing ---</h2><p>The elves are testing the downloader.</p></article>
+<p>Your puzzle answer was <code>24000</code>.</p><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Now <em>again</em>.</p></article>
+</main>
+</body>
+</html>
+";
+
+const INPUT: &str = "1000\n2000\n\n3000\n";
+
+fn pages() -> HashMap<&'static str, &'static str> {
+    HashMap::from([
+        ("/2022/day/1", DESCRIPTION),
+        ("/2022/day/1/input", INPUT),
+        ("/2022/day/2", DESCRIPTION_PART2),
+    ])
+}
+
+#[test]
+fn download_html() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) = start_server(pages());
+    let dir = setup("html", &base_url);
+
+    Puzzle::new(2022, 1)
+        .dl_html(DLOpt::IfNoExist)
+        .expect("download failed");
+    let html = fs::read_to_string(dir.join("descriptions/day1.html")).unwrap();
+    assert_eq!(html, DESCRIPTION);
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].0, "GET /2022/day/1 HTTP/1.1");
+    assert_eq!(requests[0].1.get("cookie").unwrap(), "session=test-session");
+    assert!(requests[0].1.get("user-agent").unwrap().contains("run-aoc"));
+}
+
+#[test]
+fn download_html_skips_existing() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) = start_server(pages());
+    let dir = setup("html-existing", &base_url);
+    let existing = "<h2 id=\"part2\">existing</h2>";
+    fs::write(dir.join("descriptions/day1.html"), existing).unwrap();
+
+    Puzzle::new(2022, 1)
+        .dl_html(DLOpt::IfNoExist)
+        .expect("download failed");
+    let html = fs::read_to_string(dir.join("descriptions/day1.html")).unwrap();
+    assert_eq!(html, existing);
+    assert_eq!(requests.lock().unwrap().len(), 0);
+
+    Puzzle::new(2022, 1)
+        .dl_html(DLOpt::Force)
+        .expect("download failed");
+    let html = fs::read_to_string(dir.join("descriptions/day1.html")).unwrap();
+    assert_eq!(html, DESCRIPTION);
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn download_html_again_for_part2() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) = start_server(pages());
+    let dir = setup("html-part2", &base_url);
+    // saved before part 1 was solved
+    fs::write(dir.join("descriptions/day2.html"), DESCRIPTION).unwrap();
+
+    Puzzle::new(2022, 2)
+        .update_md(DLOpt::IfNoExist)
+        .expect("download failed");
+    let html = fs::read_to_string(dir.join("descriptions/day2.html")).unwrap();
+    assert_eq!(html, DESCRIPTION_PART2);
+    let md = fs::read_to_string(dir.join("descriptions/day2.md")).unwrap();
+    assert!(
+        md.contains("# --- Part Two ---"),
+        "unexpected markdown:\n{}",
+        md
+    );
+    assert_eq!(requests.lock().unwrap().len(), 1);
+
+    // now it has part 2, so it's not downloaded again
+    Puzzle::new(2022, 2)
+        .update_md(DLOpt::IfNoExist)
+        .expect("download failed");
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn download_md() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, _requests) = start_server(pages());
+    let dir = setup("md", &base_url);
+
+    Puzzle::new(2022, 1)
+        .dl_md(DLOpt::IfNoExist)
+        .expect("download failed");
+    let md = fs::read_to_string(dir.join("descriptions/day1.md")).unwrap();
+    assert_eq!(
+        md,
+        "# --- Day 1: Testing ---\n\nThe elves are **testing** the downloader.\n\n"
+    );
+}
+
+#[test]
+fn download_input() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) = start_server(pages());
+    let dir = setup("input", &base_url);
+
+    Puzzle::new(2022, 1)
+        .dl_input(DLOpt::IfNoExist)
+        .expect("download failed");
+    let input = fs::read_to_string(dir.join("inputs/day1-input.txt")).unwrap();
+    assert_eq!(input, INPUT);
+    assert_eq!(
+        requests.lock().unwrap()[0].0,
+        "GET /2022/day/1/input HTTP/1.1"
+    );
+
+    // not downloaded again
+    Puzzle::new(2022, 1)
+        .dl_input(DLOpt::IfNoExist)
+        .expect("download failed");
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn download_not_found() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, _requests) = start_server(pages());
+    let dir = setup("not-found", &base_url);
+
+    let err = Puzzle::new(2022, 2).dl_input(DLOpt::IfNoExist).unwrap_err();
+    assert!(err.contains("404"), "unexpected error: {}", err);
+    assert!(!dir.join("inputs/day2-input.txt").exists());
+}
+
+#[test]
+fn unlock_time() {
+    // 2022-12-01 05:00:00 UTC
+    let puzzle = Puzzle::new(2022, 1);
+    assert_eq!(puzzle.unlock_time(), 1669870800);
+    assert_eq!(
+        Puzzle::new(2022, 25).unlock_time(),
+        1669870800 + 24 * 86_400
+    );
+
+    assert_eq!(puzzle.time_until_unlock(1669870800), None);
+    assert_eq!(
+        puzzle.time_until_unlock(1669870800 - 3723),
+        Some(Duration::from_secs(3723))
+    );
+    assert_eq!(format_hms(Duration::from_secs(3723)), "01:02:03");
+    assert_eq!(
+        format_hms(Duration::from_secs(2 * 86_400 + 59)),
+        "2d 00:00:59"
+    );
+}
+
+#[test]
+fn download_before_unlock() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) = start_server(pages());
+    setup("locked", &base_url);
+
+    let err = Puzzle::new(2999, 1).dl_input(DLOpt::IfNoExist).unwrap_err();
+    assert!(
+        err.contains("day 1 unlocks in "),
+        "unexpected error: {}",
+        err
+    );
+    let err = Puzzle::new(2022, 26).dl_html(DLOpt::IfNoExist).unwrap_err();
+    assert!(
+        err.contains("no puzzle for day 26"),
+        "unexpected error: {}",
+        err
+    );
+    assert_eq!(requests.lock().unwrap().len(), 0);
+}
+
+const LOGIN_MESSAGE: &str =
+    "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+
+#[test]
+fn login_message_is_not_saved() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, _requests) = start_server_with_status(HashMap::from([
+        ("/2022/day/1/input", (400, LOGIN_MESSAGE)),
+        ("/2022/day/2/input", (200, LOGIN_MESSAGE)),
+    ]));
+    let dir = setup("login", &base_url);
+
+    for day in [1, 2] {
+        let err = Puzzle::new(2022, day)
+            .dl_input(DLOpt::IfNoExist)
+            .unwrap_err();
+        assert!(err.contains("log in"), "unexpected error: {}", err);
+    }
+    let files: Vec<_> = fs::read_dir(dir.join("inputs")).unwrap().collect();
+    assert_eq!(files.len(), 0);
+}
+
+#[test]
+fn error_pages_are_not_saved() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, _requests) = start_server_with_status(HashMap::from([
+        (
+            "/2022/day/1",
+            (200, "<!DOCTYPE html><html><body>Oops</body></html>"),
+        ),
+        ("/2022/day/1/input", (500, "Internal Server Error")),
+        ("/2022/day/2/input", (200, DESCRIPTION)),
+        ("/2022/day/3/input", (200, "")),
+    ]));
+    let dir = setup("error-pages", &base_url);
+
+    let err = Puzzle::new(2022, 1).dl_html(DLOpt::IfNoExist).unwrap_err();
+    assert!(
+        err.contains("puzzle description"),
+        "unexpected error: {}",
+        err
+    );
+    let err = Puzzle::new(2022, 1).dl_input(DLOpt::IfNoExist).unwrap_err();
+    assert!(
+        err.contains("server error (500)"),
+        "unexpected error: {}",
+        err
+    );
+    let err = Puzzle::new(2022, 2).dl_input(DLOpt::IfNoExist).unwrap_err();
+    assert!(err.contains("web page"), "unexpected error: {}", err);
+    let err = Puzzle::new(2022, 3).dl_input(DLOpt::IfNoExist).unwrap_err();
+    assert!(err.contains("empty"), "unexpected error: {}", err);
+
+    assert_eq!(fs::read_dir(dir.join("inputs")).unwrap().count(), 0);
+    assert_eq!(fs::read_dir(dir.join("descriptions")).unwrap().count(), 0);
+}
+
+#[test]
+fn bad_download_keeps_existing_file() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, _requests) =
+        start_server_with_status(HashMap::from([("/2022/day/1/input", (400, LOGIN_MESSAGE))]));
+    let dir = setup("keep-existing", &base_url);
+    fs::write(dir.join("inputs/day1-input.txt"), INPUT).unwrap();
+
+    assert!(Puzzle::new(2022, 1).dl_input(DLOpt::Force).is_err());
+    let input = fs::read_to_string(dir.join("inputs/day1-input.txt")).unwrap();
+    assert_eq!(input, INPUT);
+    assert_eq!(fs::read_dir(dir.join("inputs")).unwrap().count(), 1);
+}
+
+#[test]
+fn fetch_all_days() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) = start_server(pages());
+    let dir = setup("fetch-all", &base_url);
+    // already fetched
+    fs::write(dir.join("descriptions/day2.md"), "# Day 2\n").unwrap();
+
+    let results = fetch_all(2022, 1, 3);
+    let summary: Vec<(usize, &str, &str)> = results
+        .iter()
+        .map(|(day, what, r)| {
+            let r = match r {
+                Fetched::Fetched => "fetched",
+                Fetched::Skipped(_) => "skipped",
+                Fetched::Failed(_) => "failed",
+            };
+            (*day, *what, r)
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (1, "description", "fetched"),
+            (1, "input", "fetched"),
+            (2, "description", "skipped"),
+            (2, "input", "failed"),
+            (3, "description", "failed"),
+            (3, "input", "failed"),
+        ]
+    );
+    assert!(dir.join("descriptions/day1.md").exists());
+    assert!(dir.join("inputs/day1-input.txt").exists());
+    let num_requests = requests.lock().unwrap().len();
+
+    // run again, and only the failed ones are tried
+    // (until there are too many failures in a row)
+    let results = fetch_all(2022, 1, 4);
+    assert_eq!(results.len(), 8);
+    assert_eq!(
+        results[7],
+        (
+            4,
+            "input",
+            Fetched::Skipped(String::from("stopped after 3 failures in a row"))
+        )
+    );
+    assert_eq!(requests.lock().unwrap().len(), num_requests + 3);
+}
+
+#[test]
+fn fetch_all_keeps_part1_html() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) = start_server(pages());
+    let dir = setup("fetch-all-part1", &base_url);
+    // saved before part 1 was solved (but not converted yet)
+    fs::write(dir.join("descriptions/day1.html"), DESCRIPTION).unwrap();
+    fs::write(dir.join("inputs/day1-input.txt"), INPUT).unwrap();
+
+    let results = fetch_all(2022, 1, 1);
+    assert_eq!(results[0], (1, "description", Fetched::Fetched));
+    assert!(dir.join("descriptions/day1.md").exists());
+    Puzzle::new(2022, 1)
+        .dl_html(DLOpt::IfNoExist)
+        .expect("download failed");
+    let html = fs::read_to_string(dir.join("descriptions/day1.html")).unwrap();
+    assert_eq!(html, DESCRIPTION);
+    assert_eq!(requests.lock().unwrap().len(), 0);
+}
+
+#[test]
+fn conditional_refresh() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut page = Page::new(200, DESCRIPTION_PART2);
+    page.headers = vec![
+        ("ETag", "\"abc123\""),
+        ("Last-Modified", "Fri, 02 Dec 2022 05:00:00 GMT"),
+    ];
+    let (base_url, requests) = start_server_with_pages(HashMap::from([("/2022/day/2", page)]));
+    let dir = setup("conditional", &base_url);
+
+    let puzzle = Puzzle::new(2022, 2);
+    puzzle.dl_html(DLOpt::IfNoExist).expect("download failed");
+    assert_eq!(
+        fs::read_to_string(dir.join("descriptions/day2.html.cache")).unwrap(),
+        "ETag: \"abc123\"\nLast-Modified: Fri, 02 Dec 2022 05:00:00 GMT\n"
+    );
+
+    // forcing a refresh sends the validators, and the server says it's not modified
+    fs::write(
+        dir.join("descriptions/day2.html"),
+        DESCRIPTION_PART2.replace("again", "AGAIN"),
+    )
+    .unwrap();
+    puzzle.dl_html(DLOpt::Force).expect("download failed");
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(!requests[0].1.contains_key("if-none-match"));
+    assert_eq!(requests[1].1.get("if-none-match").unwrap(), "\"abc123\"");
+    assert_eq!(
+        requests[1].1.get("if-modified-since").unwrap(),
+        "Fri, 02 Dec 2022 05:00:00 GMT"
+    );
+    // (so the file is not downloaded again)
+    let html = fs::read_to_string(dir.join("descriptions/day2.html")).unwrap();
+    assert!(html.contains("AGAIN"));
+}
+
+// "1000\n2000\n\n3000\n", gzipped
+const INPUT_GZIP: [u8; 31] = [
+    31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 51, 52, 48, 48, 224, 50, 2, 17, 92, 198, 32, 18, 0, 228, 224,
+    68, 130, 16, 0, 0, 0,
+];
+
+#[test]
+fn gzip_response() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let page = Page {
+        status: 200,
+        headers: vec![("Content-Encoding", "gzip")],
+        body: INPUT_GZIP.to_vec(),
+    };
+    let (base_url, requests) =
+        start_server_with_pages(HashMap::from([("/2022/day/1/input", page)]));
+    let dir = setup("gzip", &base_url);
+
+    Puzzle::new(2022, 1)
+        .dl_input(DLOpt::IfNoExist)
+        .expect("download failed");
+    let input = fs::read_to_string(dir.join("inputs/day1-input.txt")).unwrap();
+    assert_eq!(input, INPUT);
+    let requests = requests.lock().unwrap();
+    assert!(requests[0]
+        .1
+        .get("accept-encoding")
+        .unwrap()
+        .contains("gzip"));
+}
@@ -0,0 +1,74 @@
+// encrypted inputs, with the key in a temp home directory
+
+use std::env;
+use std::fs;
+
+use run_aoc::encrypt;
+use run_aoc::input::read_input;
+
+mod common;
+use common::{setup, LOCK};
+
+const INPUT: &str = "1000\n2000\n\n3000\n";
+
+#[test]
+fn encrypt_and_decrypt_inputs() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = setup("encrypt", "http://127.0.0.1:1");
+    env::remove_var("AOC_INPUTS_KEY");
+    fs::write(dir.join("inputs/day1-input.txt"), INPUT).unwrap();
+    fs::write(dir.join("inputs/day1-example.txt"), "1\n").unwrap();
+
+    // creates the key
+    encrypt::encrypt_inputs().expect("encrypt failed");
+    let key = fs::read_to_string(dir.join(".aoc-inputs-key")).unwrap();
+    assert_eq!(key.trim().len(), 64);
+    let encrypted = fs::read(dir.join("inputs/day1-input.txt.enc")).unwrap();
+    assert!(!String::from_utf8_lossy(&encrypted).contains("2000"));
+    // (the examples are not encrypted)
+    assert!(!dir.join("inputs/day1-example.txt.enc").exists());
+
+    // not changed if the input is the same
+    encrypt::encrypt_inputs().expect("encrypt failed");
+    assert_eq!(
+        fs::read(dir.join("inputs/day1-input.txt.enc")).unwrap(),
+        encrypted
+    );
+
+    // without the plaintext, the encrypted one is read
+    fs::remove_file(dir.join("inputs/day1-input.txt")).unwrap();
+    assert_eq!(read_input("inputs/day1-input.txt").unwrap(), INPUT);
+
+    encrypt::decrypt_inputs().expect("decrypt failed");
+    assert_eq!(
+        fs::read_to_string(dir.join("inputs/day1-input.txt")).unwrap(),
+        INPUT
+    );
+}
+
+#[test]
+fn wrong_key() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = setup("encrypt-wrong-key", "http://127.0.0.1:1");
+    env::set_var("AOC_INPUTS_KEY", "11".repeat(32));
+    fs::write(dir.join("inputs/day1-input.txt"), INPUT).unwrap();
+    encrypt::encrypt_inputs().expect("encrypt failed");
+    fs::remove_file(dir.join("inputs/day1-input.txt")).unwrap();
+
+    env::set_var("AOC_INPUTS_KEY", "22".repeat(32));
+    let err = read_input("inputs/day1-input.txt").unwrap_err();
+    assert!(
+        err.contains("decryption failed"),
+        "unexpected error: {}",
+        err
+    );
+
+    env::set_var("AOC_INPUTS_KEY", "not hex");
+    let err = read_input("inputs/day1-input.txt").unwrap_err();
+    assert!(
+        err.contains("64 hex characters"),
+        "unexpected error: {}",
+        err
+    );
+    env::remove_var("AOC_INPUTS_KEY");
+}
@@ -0,0 +1,96 @@
+// the time between requests, which is kept in a log in the home directory
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use run_aoc::download::DLOpt;
+use run_aoc::puzzle::Puzzle;
+
+mod common;
+use common::{setup, start_server, LOCK};
+
+const DESCRIPTION: &str = "<!DOCTYPE html>
+<html lang=\"en-us\">
+<body>
+<main>
+<article class=\"day-desc\"><h2>--- Day 1: Testing ---</h2><p>Throttled.</p></article>
+</main>
+</body>
+</html>
+";
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis()
+}
+
+// how long the download took
+fn timed_download() -> Duration {
+    let start = Instant::now();
+    Puzzle::new(2022, 1)
+        .dl_html(DLOpt::Force)
+        .expect("download failed");
+    start.elapsed()
+}
+
+#[test]
+fn waits_for_the_interval() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, _) = start_server(HashMap::from([("/2022/day/1", DESCRIPTION)]));
+    let dir = setup("governor-wait", &base_url);
+    env::set_var("AOC_REQUEST_INTERVAL", "1");
+
+    // the last request was just now
+    let log = format!("{}\tGET\t{base_url}/2022/day/2\n", now_millis());
+    fs::write(dir.join(".aoc-request-log"), log).unwrap();
+    assert!(timed_download() >= Duration::from_millis(900));
+
+    // the last request was a while ago
+    let log = format!("{}\tGET\t{base_url}/2022/day/2\n", now_millis() - 5000);
+    fs::write(dir.join(".aoc-request-log"), log).unwrap();
+    assert!(timed_download() < Duration::from_millis(900));
+
+    let log = fs::read_to_string(dir.join(".aoc-request-log")).unwrap();
+    let lines: Vec<&str> = log.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].ends_with(&format!("\tGET\t{base_url}/2022/day/1")));
+    env::set_var("AOC_REQUEST_INTERVAL", "0");
+}
+
+#[test]
+fn bad_interval() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) = start_server(HashMap::from([("/2022/day/1", DESCRIPTION)]));
+    setup("governor-bad", &base_url);
+    env::set_var("AOC_REQUEST_INTERVAL", "soon");
+
+    let err = Puzzle::new(2022, 1).dl_html(DLOpt::Force).unwrap_err();
+    assert_eq!(err, "could not parse request interval 'soon' as a number");
+    assert_eq!(requests.lock().unwrap().len(), 0);
+    env::set_var("AOC_REQUEST_INTERVAL", "0");
+}
+
+#[test]
+fn old_entries_are_pruned() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, _) = start_server(HashMap::from([("/2022/day/1", DESCRIPTION)]));
+    let dir = setup("governor-prune", &base_url);
+
+    let log: String = (0..150)
+        .map(|i| format!("{}\tGET\t{base_url}/old/{i}\n", 1_600_000_000_000u64 + i))
+        .collect();
+    fs::write(dir.join(".aoc-request-log"), log).unwrap();
+    timed_download();
+
+    // only the most recent 100 are kept
+    let log = fs::read_to_string(dir.join(".aoc-request-log")).unwrap();
+    let lines: Vec<&str> = log.lines().collect();
+    assert_eq!(lines.len(), 100);
+    assert!(lines[0].ends_with("/old/51"), "{}", lines[0]);
+    assert!(lines[98].ends_with("/old/149"), "{}", lines[98]);
+    assert!(lines[99].ends_with("/2022/day/1"), "{}", lines[99]);
+}
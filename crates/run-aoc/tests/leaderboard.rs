@@ -0,0 +1,135 @@
+// fetch a private leaderboard from a local stand-in for the AoC server
+
+use std::collections::HashMap;
+use std::fs;
+
+use run_aoc::leaderboard;
+
+mod common;
+use common::{setup, start_server, LOCK};
+
+const LEADERBOARD: &str = r#"{
+  "event": "2022",
+  "owner_id": 101,
+  "members": {
+    "101": {
+      "id": 101,
+      "name": "Owner",
+      "stars": 3,
+      "local_score": 10,
+      "global_score": 0,
+      "last_star_ts": 1669960000,
+      "completion_day_level": {
+        "1": {
+          "1": { "get_star_ts": 1669871000, "star_index": 1 },
+          "2": { "get_star_ts": 1669872000, "star_index": 2 }
+        },
+        "2": {
+          "1": { "get_star_ts": 1669960000, "star_index": 3 }
+        }
+      }
+    },
+    "202": {
+      "id": 202,
+      "name": null,
+      "stars": 4,
+      "local_score": 12,
+      "global_score": 0,
+      "last_star_ts": 1669958000,
+      "completion_day_level": {
+        "1": {
+          "1": { "get_star_ts": 1669870000, "star_index": 1 },
+          "2": { "get_star_ts": 1669870500, "star_index": 2 }
+        },
+        "2": {
+          "1": { "get_star_ts": 1669957000, "star_index": 3 },
+          "2": { "get_star_ts": 1669958000, "star_index": 4 }
+        }
+      }
+    },
+    "303": {
+      "id": 303,
+      "name": "Lurker",
+      "stars": 0,
+      "local_score": 0,
+      "global_score": 0,
+      "last_star_ts": 0,
+      "completion_day_level": {}
+    }
+  }
+}"#;
+
+#[test]
+fn fetch_and_cache() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) = start_server(HashMap::from([(
+        "/2022/leaderboard/private/view/101.json",
+        LEADERBOARD,
+    )]));
+    let dir = setup("leaderboard", &base_url);
+
+    let json = leaderboard::fetch(2022, "101").expect("fetch failed");
+    assert_eq!(json, LEADERBOARD);
+    assert_eq!(
+        fs::read_to_string(dir.join("leaderboard-101.json")).unwrap(),
+        LEADERBOARD
+    );
+    assert_eq!(requests.lock().unwrap().len(), 1);
+
+    // cached, so not fetched again
+    let json = leaderboard::fetch(2022, "101").expect("fetch failed");
+    assert_eq!(json, LEADERBOARD);
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn not_logged_in() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, _requests) = start_server(HashMap::from([(
+        "/2022/leaderboard/private/view/101.json",
+        "<!DOCTYPE html><html><body>To play, please identify yourself</body></html>",
+    )]));
+    let dir = setup("leaderboard-login", &base_url);
+
+    let err = leaderboard::fetch(2022, "101").unwrap_err();
+    assert!(err.contains("could not parse"), "unexpected error: {}", err);
+    assert!(!dir.join("leaderboard-101.json").exists());
+}
+
+#[test]
+fn table() {
+    let lb = leaderboard::parse(LEADERBOARD).unwrap();
+    let lines: Vec<String> = leaderboard::table(&lb)
+        .lines()
+        .map(|l| l.trim_end().to_string())
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            "rank  score  stars  1        10        20   25  last star (UTC)      name",
+            "   1     12      4  **.......................  2022-12-02 05:13:20  (anonymous user #202)",
+            "   2     10      3  *+.......................  2022-12-02 05:46:40  Owner",
+            "   3      0      0  .........................  -                    Lurker",
+        ]
+    );
+}
+
+#[test]
+fn day_table() {
+    let lb = leaderboard::parse(LEADERBOARD).unwrap();
+    let lines: Vec<String> = leaderboard::day_table(&lb, 2)
+        .lines()
+        .map(|l| l.trim_end().to_string())
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            "Day 2",
+            "",
+            "rank  part 1 (UTC)         part 2 (UTC)         name",
+            "   1  2022-12-02 04:56:40  2022-12-02 05:13:20  (anonymous user #202)",
+            "   2  2022-12-02 05:46:40  -                    Owner",
+            "   3  -                    -                    Lurker",
+        ]
+    );
+}
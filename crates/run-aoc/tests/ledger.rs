@@ -0,0 +1,103 @@
+// the guess ledger, which stops answers that can't be right from being submitted
+
+use std::fs;
+
+use run_aoc::cli::Part;
+use run_aoc::ledger::{check, record};
+use run_aoc::submit::Verdict;
+
+mod common;
+use common::{setup, LOCK};
+
+const GUESSES: &str = "1670000000\t1\tone\t24000\tcorrect
+1670000100\t2\tone\tabc\tincorrect
+1670000200\t2\tone\t500\ttoo-high
+1670000300\t2\tone\t800\ttoo-high
+1670000400\t2\tone\t100\ttoo-low
+1670000500\t2\tone\t50\ttoo-low
+1670000600\t2\tone\t300\trate-limited
+";
+
+#[test]
+fn already_correct() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = setup("ledger-correct", "http://127.0.0.1:1");
+    fs::write(dir.join("guesses.txt"), GUESSES).unwrap();
+
+    assert_eq!(
+        check(1, Part::One, "12345"),
+        Err(String::from(
+            "day 1 part one is already solved: '24000' was submitted at 2022-12-02 16:53:20 UTC (correct!)"
+        ))
+    );
+    // (only for that part)
+    assert_eq!(check(1, Part::Two, "12345"), Ok(()));
+}
+
+#[test]
+fn already_wrong() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = setup("ledger-wrong", "http://127.0.0.1:1");
+    fs::write(dir.join("guesses.txt"), GUESSES).unwrap();
+
+    let err = check(2, Part::One, "abc").unwrap_err();
+    assert!(
+        err.starts_with("not submitting, 'abc' was submitted at"),
+        "{}",
+        err
+    );
+    let err = check(2, Part::One, "500").unwrap_err();
+    assert!(err.ends_with("(incorrect (too high))"), "{}", err);
+    // (rate limited means it wasn't checked)
+    assert_eq!(check(2, Part::One, "300"), Ok(()));
+}
+
+#[test]
+fn too_high_and_too_low() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = setup("ledger-bounds", "http://127.0.0.1:1");
+    fs::write(dir.join("guesses.txt"), GUESSES).unwrap();
+
+    // (the closest bound is the one that's shown)
+    let err = check(2, Part::One, "600").unwrap_err();
+    assert!(
+        err.starts_with("not submitting '600', it can't be right because '500' was submitted"),
+        "{}",
+        err
+    );
+    let err = check(2, Part::One, "75").unwrap_err();
+    assert!(
+        err.starts_with("not submitting '75', it can't be right because '100' was submitted"),
+        "{}",
+        err
+    );
+    assert!(check(2, Part::One, "100").is_err());
+    assert_eq!(check(2, Part::One, "101"), Ok(()));
+    assert_eq!(check(2, Part::One, "499"), Ok(()));
+    // (the bounds are only for numbers)
+    assert_eq!(check(2, Part::One, "xyz"), Ok(()));
+}
+
+#[test]
+fn record_guesses() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    setup("ledger-record", "http://127.0.0.1:1");
+
+    assert_eq!(check(3, Part::Two, "42"), Ok(()));
+    record(3, Part::Two, "42", Verdict::TooLow).unwrap();
+    assert!(check(3, Part::Two, "41").is_err());
+    assert_eq!(check(3, Part::Two, "43"), Ok(()));
+    record(3, Part::Two, "43", Verdict::Correct).unwrap();
+    assert!(check(3, Part::Two, "44").is_err());
+}
+
+#[test]
+fn bad_line() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = setup("ledger-bad", "http://127.0.0.1:1");
+    fs::write(dir.join("guesses.txt"), "1670000000\t1\tone\t5\tmaybe\n").unwrap();
+    assert_eq!(
+        check(1, Part::One, "5"),
+        Err(String::from("guesses.txt line 1: unknown verdict 'maybe'"))
+    );
+}
@@ -0,0 +1,213 @@
+// convert downloaded descriptions to markdown
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+
+use run_aoc::download::DLOpt;
+use run_aoc::parse::{html_to_md, ParseMode};
+use run_aoc::puzzle::Puzzle;
+
+mod common;
+use common::{setup, start_server, LOCK};
+
+fn description(article: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en-us\">
+<head><title>Day 1 - Advent of Code 2022</title></head>
+<body>
+<main>
+<article class=\"day-desc\"><h2>--- Day 1: Testing ---</h2>{article}</article>
+<p>Your puzzle answer was <code>24000</code>.</p><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Now <em>again</em>.</p></article>
+</main>
+</body>
+</html>
+"
+    )
+}
+
+// the description is saved in the 2022 directory, with the other years next to it
+fn convert(name: &str, article: &str) -> String {
+    let html = description(article);
+    let html: &'static str = Box::leak(html.into_boxed_str());
+    let (base_url, _) = start_server(HashMap::from([("/2022/day/1", html)]));
+    let dir = setup(name, &base_url);
+    fs::create_dir_all(dir.join("2019/descriptions")).unwrap();
+    fs::write(dir.join("2019/descriptions/day5.md"), "# Day 5").unwrap();
+    fs::create_dir_all(dir.join("2022/descriptions")).unwrap();
+    env::set_current_dir(dir.join("2022")).unwrap();
+
+    Puzzle::new(2022, 1)
+        .dl_md(DLOpt::IfNoExist)
+        .expect("conversion failed");
+    fs::read_to_string(dir.join("2022/descriptions/day1.md")).unwrap()
+}
+
+#[test]
+fn links_to_days() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let md = convert(
+        "md-days",
+        "<p>See <a href=\"3\">day 3</a>, <a href=\"/2022/day/4\">day 4</a>, <a href=\"/2019/day/5\">this</a> and <a href=\"/2018/day/6\">that</a>.</p>",
+    );
+    assert!(md.contains("See [day 3](./day3.md), [day 4](./day4.md), [this](../../2019/descriptions/day5.md) and [that](https://adventofcode.com/2018/day/6)."));
+}
+
+#[test]
+fn links_to_other_pages() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let md = convert(
+        "md-pages",
+        "<p><a href=\"/2022/about\">About</a> <a href=\"#part2\">part 2</a> <a href=\"/2019/events\">events</a> <a href=\"/2022/leaderboard?x=1\">leaderboard</a> <a href=\"?y=2\">query</a> <a href=\"2/input\">input</a> <a href=\"https://example.com/\">elsewhere</a> <a href=\"//example.com/x\">same protocol</a> <a href=\"/2022/day/3#part2\">day 3 part 2</a> <a href=\"5#part2\">day 5 part 2</a></p>",
+    );
+    assert!(md.contains("[About](https://adventofcode.com/2022/about) [part 2](#part2) [events](https://adventofcode.com/2019/events) [leaderboard](https://adventofcode.com/2022/leaderboard?x=1) [query](https://adventofcode.com/2022/day/1?y=2) [input](https://adventofcode.com/2022/day/2/input) [elsewhere](https://example.com/) [same protocol](https://example.com/x) [day 3 part 2](./day3.md#part2) [day 5 part 2](./day5.md#part2)"));
+}
+
+#[test]
+fn character_references() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let md = convert(
+        "md-entities",
+        "<p>Fish &amp; chips, the elf&#39;s&nbsp;map &mdash; left &#x2192; right, a &lt;b&gt; tag, some *stars* and snake_case [brackets] &bogus; &#0; &#xD800; &#99999999; &lbrack;html5&rbrack; &rarrw;.</p>
+<p>Run <code>a &amp;&amp; b</code> or <code><em>x &lt; `y`</em></code> or <code>1 &lt; <em>2</em></code>, <span title=\"&quot;Quoted&quot; &amp; more\">hover</span>.</p>
+<pre><code>if a &lt; b &amp;&amp; c &gt; d {}
+</code></pre>
+<pre><code>x &lt; <em>y &amp; z</em> <span title=\"a &quot;title&quot;\">&gt;</span>
+</code></pre>
+<p>See <a href=\"?a=1&amp;b=2\">this</a>, <a href=\"https://example.com/?x=1&amp;y=2\" target=\"_blank\">that</a> and <a href=\"https://example.com/?z&#61;3\" target=\"_blank\"><span title=\"more\">more</span></a>.</p>
+",
+    );
+    assert!(md.contains(
+        "Fish \\& chips, the elf's\u{a0}map — left → right, a \\<b\\> tag, some \\*stars\\* and snake\\_case \\[brackets\\] \\&bogus; \u{fffd} \u{fffd} \u{fffd} \\[html5\\] ↝.\n\n"
+    ));
+    assert!(md.contains("Run `a && b` or **`` x < `y` ``** or <code>1 &lt; <b>2</b></code>, [hover](# \"\\\"Quoted\\\" & more\").\n\n"));
+    assert!(md.contains("```\nif a < b && c > d {}\n```\n\n"));
+    assert!(md.contains("<pre><code>x &lt; <b>y &amp; z</b> <a href=\"#\" alt=\"a &quot;title&quot;\">&gt;</a>\n</code></pre>\n\n"));
+    assert!(md.contains("See [this](https://adventofcode.com/2022/day/1?a=1&b=2), [that](https://example.com/?x=1&y=2) and [more](https://example.com/?z=3 \"more\")."));
+}
+
+#[test]
+fn lists() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let md = convert(
+        "md-lists",
+        "<ol start=\"1\">
+<li>First <em>one</em></li>
+<li>Second
+<ul>
+<li>nested</li>
+<li>nested again<ol><li>three deep</li></ol></li>
+</ul>
+</li>
+<li><code>third</code></li>
+</ol>
+<ol start=\"5\">
+<li>Fifth</li>
+<li class=\"x\">Sixth<ol start=\"0\"><li>zero</li><li>one</li></ol></li>
+</ol>
+",
+    );
+    assert!(md.contains(
+        "1. First **one**
+2. Second
+    * nested
+    * nested again
+        1. three deep
+3. `third`
+
+5. Fifth
+6. Sixth
+    0. zero
+    1. one
+
+"
+    ));
+}
+
+#[test]
+fn other_elements() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let md = convert(
+        "md-other",
+        "<h3>Subheading</h3>
+<blockquote><p>Quoted <i>words</i>.</p><p>More.</p></blockquote>
+<p>10<sup>6</sup> <s>wrong</s> right</p>
+<table>
+<tr><th>Name</th><th>Score</th></tr>
+<tr><td>Alice</td><td><code>a|b</code></td></tr>
+<tbody><tr><td>Bob</td></tr></tbody>
+</table>
+",
+    );
+    assert!(md.contains(
+        "## Subheading
+
+> Quoted *words*.
+>
+> More.
+
+10<sup>6</sup> ~~wrong~~ right
+
+| Name | Score |
+| --- | --- |
+| Alice | `a\\|b` |
+| Bob |  |
+
+"
+    ));
+}
+
+#[test]
+fn parse_error_location() {
+    let html = description("<p>Some <b>bold</b> text.</p>");
+    let err = html_to_md(&html, &Puzzle::new(2022, 1), ParseMode::Strict).unwrap_err();
+    assert_eq!(
+        err,
+        "Could not parse html at line 6, column 58: paragraph failed at line 6, column 66
+  ><h2>--- Day 1: Testing ---</h2><p>Some <b>bold</b> text.</p></article>
+                                          ^"
+    );
+}
+
+#[test]
+fn unknown_markup() {
+    let html = description(
+        "<p>Some <b>bold</b> and <em>very <b class=\"x\">bold</b></em>.<br/></p>
+<div class=\"note\"><div><p>Note</p></div></div><p>After</p>
+<pre><code></code></pre>",
+    );
+    let (md, warnings) =
+        html_to_md(&html, &Puzzle::new(2022, 1), ParseMode::Lenient).expect("conversion failed");
+    assert!(md.contains(
+        "Some bold and **very bold**.
+
+<div class=\"note\"><div><p>Note</p></div></div>
+
+After
+
+<pre><code></code></pre>
+
+Your puzzle answer was `24000`.
+
+"
+    ));
+    assert_eq!(
+        warnings,
+        vec![
+            "stripped <b> in paragraph at line 6, column 66",
+            "stripped <b class=\"x\"> in paragraph at line 6, column 91",
+            "stripped <br/> in paragraph at line 6, column 118",
+            "kept <div class=\"note\"> as HTML at line 7, column 1",
+            // (the tags are known, so they're kept instead of stripped)
+            "kept <pre> as HTML at line 8, column 1",
+        ]
+    );
+}
+
+#[test]
+fn unknown_markup_downloaded() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let md = convert("md-unknown", "<p>Some <b>bold</b> text.</p>");
+    assert!(md.contains("Some bold text.\n\n"));
+}
@@ -0,0 +1,144 @@
+// the star calendar, from a local stand-in for the AoC server
+
+use std::collections::HashMap;
+use std::fs;
+
+use run_aoc::status;
+
+mod common;
+use common::{setup, start_server, LOCK};
+
+const CALENDAR: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<pre class="calendar">
+<a aria-label="Day 1, two stars" href="/2022/day/1" class="calendar-day1 calendar-verycomplete">  ~~~  <span class="calendar-day"> 1</span> <span class="calendar-mark-complete">*</span><span class="calendar-mark-verycomplete">*</span></a>
+<a aria-label="Day 2, one star" href="/2022/day/2" class="calendar-day2 calendar-complete">  ~~~  <span class="calendar-day"> 2</span> <span class="calendar-mark-complete">*</span><span class="calendar-mark-verycomplete">*</span></a>
+<a aria-label="Day 3" href="/2022/day/3" class="calendar-day3">  ~~~  <span class="calendar-day"> 3</span> <span class="calendar-mark-complete">*</span><span class="calendar-mark-verycomplete">*</span></a>
+<span aria-hidden="true" class="calendar-day4">                           <span class="calendar-day"> 4</span></span>
+</pre>
+</main>
+</body>
+</html>
+"#;
+
+const EVENTS: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<article><p>Here are the events:</p></article>
+<div class="eventlist-event"><a href="/2022">[2022]</a> <span class="star-count">5*</span></div>
+<div class="eventlist-event"><a href="/2021">[2021]</a> <span class="star-count">50*</span></div>
+<div class="eventlist-event"><a href="/2020">[2020]</a></div>
+</main>
+</body>
+</html>
+"#;
+
+#[test]
+fn parse_calendar() {
+    assert_eq!(
+        status::parse_calendar(CALENDAR).unwrap(),
+        vec![(1, 2), (2, 1), (3, 0)]
+    );
+    assert!(status::parse_calendar("<html>To play, please identify yourself</html>").is_err());
+}
+
+#[test]
+fn parse_events() {
+    assert_eq!(
+        status::parse_events(EVENTS).unwrap(),
+        vec![(2022, 5), (2021, 50), (2020, 0)]
+    );
+}
+
+#[test]
+fn show_status() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (base_url, requests) =
+        start_server(HashMap::from([("/2022", CALENDAR), ("/events", EVENTS)]));
+    let dir = setup("status", &base_url);
+    fs::write(dir.join("inputs/day1-input.txt"), "1\n").unwrap();
+
+    let output = status::render(2022, true).expect("status failed");
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines[..3],
+        ["2022: 3 stars", "", " 1 **    2 *     3 .     4       5"]
+    );
+    assert_eq!(
+        lines[8..11],
+        [
+            "day  stars  description  input  tests (1/2)  notes",
+            "  1  **     -            yes    0/0          no part 1 tests, no part 2 tests",
+            "  2  *      -            -      0/0          no input, no part 1 tests",
+        ]
+    );
+    assert_eq!(
+        lines[lines.len() - 4..],
+        ["", "2022:  5*", "2021: 50*", "2020:  0*"]
+    );
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0].0, "GET /2022 HTTP/1.1");
+    assert_eq!(requests[1].0, "GET /events HTTP/1.1");
+}
+
+#[test]
+fn count_tests() {
+    let source = "mod tests {
+    test_fn!(day10, part1, example, 13140);
+    test_fn!(day10, part1_SLOW, input, 14320);
+    // test_fn!(day10, part1, other, 1);
+    test_fn!(day1, part1, input, 1);
+
+    test_fn!(
+        day10,
+        part2,
+        input,
+        \"###...##..
+#..#.#..#.\"
+    );
+}
+";
+    assert_eq!(status::count_tests(source, 10, "part1"), 2);
+    assert_eq!(status::count_tests(source, 10, "part2"), 1);
+    assert_eq!(status::count_tests(source, 1, "part2"), 0);
+}
+
+#[test]
+fn tables() {
+    let days: Vec<status::DayStatus> = [(1, Some(2)), (2, Some(1)), (3, Some(0)), (4, None)]
+        .into_iter()
+        .chain((5..=25).map(|d| (d, None)))
+        .map(|(day, stars)| status::DayStatus {
+            day,
+            stars,
+            has_html: day == 1,
+            has_md: day <= 2,
+            has_input: day == 1,
+            part1_tests: if day == 1 { 2 } else { 0 },
+            part2_tests: if day == 1 { 2 } else { 0 },
+        })
+        .collect();
+
+    let grid = status::calendar_grid(2022, &days);
+    let grid: Vec<&str> = grid.lines().collect();
+    assert_eq!(
+        grid[..3],
+        ["2022: 3 stars", "", " 1 **    2 *     3 .     4       5",]
+    );
+
+    let table = status::local_table(&days);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(
+        lines[..5],
+        [
+            "day  stars  description  input  tests (1/2)  notes",
+            "  1  **     html, md     yes    2/2",
+            "  2  *      md           -      0/0          no input, no part 1 tests",
+            "  3  .      -            -      0/0",
+            "  4  -      -            -      0/0",
+        ]
+    );
+}
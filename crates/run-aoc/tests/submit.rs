@@ -0,0 +1,81 @@
+// the responses to a submitted answer
+
+use std::time::Duration;
+
+use run_aoc::submit::{parse_response, Verdict};
+
+// (the responses are the usual page, with the message in the <article>)
+fn response(article: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en-us\">
+<head><title>Day 1 - Advent of Code 2022</title></head>
+<body>
+<main>
+<article><p>{article}</p></article>
+</main>
+</body>
+</html>
+"
+    )
+}
+
+#[test]
+fn correct() {
+    let html = response("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to collecting enough star fruit. <a href=\"/2022/day/1#part2\">[Continue to Part Two]</a>");
+    assert_eq!(parse_response(&html), Verdict::Correct);
+}
+
+#[test]
+fn incorrect() {
+    let html = response("That's not the right answer.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2022/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>");
+    assert_eq!(parse_response(&html), Verdict::Incorrect);
+}
+
+#[test]
+fn too_high() {
+    let html = response("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>");
+    assert_eq!(parse_response(&html), Verdict::TooHigh);
+}
+
+#[test]
+fn too_low() {
+    let html = response("That's not the right answer; your answer is too low.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>");
+    assert_eq!(parse_response(&html), Verdict::TooLow);
+}
+
+#[test]
+fn already_solved() {
+    let html = response("You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2022/day/1\">[Return to Day 1]</a>");
+    assert_eq!(parse_response(&html), Verdict::AlreadySolved);
+}
+
+#[test]
+fn rate_limited() {
+    let html = response("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 12s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a>");
+    assert_eq!(
+        parse_response(&html),
+        Verdict::RateLimited(Duration::from_secs(252))
+    );
+    let html = response("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 37s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a>");
+    assert_eq!(
+        parse_response(&html),
+        Verdict::RateLimited(Duration::from_secs(37))
+    );
+    // (without the time, it's not clear how long)
+    let html = response("You gave an answer too recently; you have to wait after submitting an answer before trying again.");
+    assert!(matches!(parse_response(&html), Verdict::Unknown(_)));
+}
+
+#[test]
+fn unknown() {
+    let html = response("Something <em>new</em> happened.");
+    assert_eq!(
+        parse_response(&html),
+        Verdict::Unknown(String::from("Something new happened."))
+    );
+    assert_eq!(
+        parse_response("<html>To play, please identify yourself</html>"),
+        Verdict::Unknown(String::from("(no <article> in the response)"))
+    );
+}
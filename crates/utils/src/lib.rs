@@ -8,6 +8,8 @@ use nom::combinator::recognize;
 use nom::sequence::tuple;
 use nom::IResult;
 
+pub mod ocr;
+pub mod rng;
 pub mod traits;
 
 // find GCD of 2 numbers
@@ -0,0 +1,232 @@
+// recognize the block letters that some puzzles use as their output
+// (like the CRT display in 2022 day 10)
+//
+// the letters are separated by (at least) one unlit column, so the grid is split
+// into glyphs on the empty columns, and each glyph is looked up in the font
+
+// 4x6 font (Y is 5 wide)
+// (glyphs are trimmed of empty columns, so the leading column of I is gone)
+const FONT_6: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+// 6x10 font
+const FONT_10: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+// recognize letters from a string where '#' is lit, and anything else is unlit
+// (blank lines at the start and end are ignored)
+pub fn ocr_str(s: &str) -> Result<String, String> {
+    let grid: Vec<Vec<bool>> = s
+        .trim_matches('\n')
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#').collect())
+        .collect();
+    ocr(&grid)
+}
+
+// recognize letters from a grid of lit (true) and unlit (false) cells
+// (the grid height determines the font: 6 or 10 rows)
+pub fn ocr(grid: &[Vec<bool>]) -> Result<String, String> {
+    let font: &[(char, &str)] = match grid.len() {
+        6 => &FONT_6,
+        10 => &FONT_10,
+        n => {
+            return Err(format!("expected a grid with 6 or 10 rows, found {}", n));
+        }
+    };
+    let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    let lit = |row: usize, col: usize| *grid[row].get(col).unwrap_or(&false);
+    let empty_col = |col: usize| (0..grid.len()).all(|row| !lit(row, col));
+
+    let mut letters = String::new();
+    let mut col = 0;
+    while col < width {
+        if empty_col(col) {
+            col += 1;
+            continue;
+        }
+        let start = col;
+        while col < width && !empty_col(col) {
+            col += 1;
+        }
+        let glyph = (0..grid.len())
+            .map(|row| {
+                (start..col)
+                    .map(|c| if lit(row, c) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        match font.iter().find(|(_, g)| *g == glyph) {
+            Some((letter, _)) => letters.push(*letter),
+            None => {
+                return Err(format!(
+                    "unrecognized glyph at column {}:\n{}",
+                    start, glyph
+                ));
+            }
+        }
+    }
+    if letters.is_empty() {
+        return Err(String::from("no lit cells found"));
+    }
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the glyphs side by side, with a couple of unlit columns between them
+    fn render(font: &[(char, &str)], letters: &str) -> String {
+        let glyphs: Vec<Vec<&str>> = letters
+            .chars()
+            .map(|l| {
+                let (_, g) = font.iter().find(|(c, _)| *c == l).unwrap();
+                g.lines().collect()
+            })
+            .collect();
+        (0..glyphs[0].len())
+            .map(|row| {
+                glyphs
+                    .iter()
+                    .map(|g| g[row])
+                    .collect::<Vec<&str>>()
+                    .join("..")
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn font_6() {
+        let letters: String = FONT_6.iter().map(|(c, _)| c).collect();
+        assert_eq!(ocr_str(&render(&FONT_6, &letters)), Ok(letters));
+        assert_eq!(
+            ocr_str(
+                "
+###...##..###..###..#..#..##..###....##.
+#..#.#..#.#..#.#..#.#.#..#..#.#..#....#.
+#..#.#....#..#.###..##...#..#.#..#....#.
+###..#....###..#..#.#.#..####.###.....#.
+#....#..#.#....#..#.#.#..#..#.#....#..#.
+#.....##..#....###..#..#.#..#.#.....##..
+"
+            ),
+            Ok(String::from("PCPBKAPJ"))
+        );
+    }
+
+    #[test]
+    fn font_10() {
+        let letters: String = FONT_10.iter().map(|(c, _)| c).collect();
+        assert_eq!(ocr_str(&render(&FONT_10, &letters)), Ok(letters));
+        // (using '.' or ' ' for unlit doesn't matter)
+        let hz = render(&FONT_10, "HZ").replace('.', " ");
+        assert_eq!(ocr_str(&hz), Ok(String::from("HZ")));
+    }
+
+    #[test]
+    fn uneven_rows() {
+        // (the missing cells at the end of the short rows are unlit)
+        let rows = render(&FONT_6, "LU");
+        let trimmed: Vec<&str> = rows.lines().map(|l| l.trim_end_matches('.')).collect();
+        assert_ne!(trimmed[0].len(), trimmed[5].len());
+        assert_eq!(ocr_str(&trimmed.join("\n")), Ok(String::from("LU")));
+
+        let mut grid: Vec<Vec<bool>> = vec![vec![]; 6];
+        grid[0] = vec![false; 3];
+        assert_eq!(ocr(&grid), Err(String::from("no lit cells found")));
+    }
+
+    #[test]
+    fn unknown_glyphs() {
+        let err = ocr_str(&render(&FONT_6, "AB").replace("####", "#.##")).unwrap_err();
+        assert_eq!(
+            err,
+            "unrecognized glyph at column 0:\n.##.\n#..#\n#..#\n#.##\n#..#\n#..#"
+        );
+        assert_eq!(
+            ocr_str("#..#\n####\n#..#"),
+            Err(String::from("expected a grid with 6 or 10 rows, found 3"))
+        );
+    }
+}
@@ -0,0 +1,57 @@
+// small deterministic random number generator, for generating test inputs
+// (SplitMix64, from http://xoshiro.di.unimi.it/splitmix64.c)
+//
+// this is not for anything that needs to be actually random, but the same seed
+// always produces the same sequence, so failing cases can be reproduced
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // random number in the range min..=max
+    pub fn range(&mut self, min: usize, max: usize) -> usize {
+        assert!(min <= max, "empty range {}..={}", min, max);
+        match ((max - min) as u64).checked_add(1) {
+            Some(span) => min + (self.next_u64() % span) as usize,
+            // (the whole range of usize, so any number will do)
+            None => self.next_u64() as usize,
+        }
+    }
+
+    // true with probability 'percent'/100
+    pub fn chance(&mut self, percent: usize) -> bool {
+        self.range(0, 99) < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        assert!(!items.is_empty(), "cannot choose from an empty slice");
+        &items[self.range(0, items.len() - 1)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range() {
+        let mut rng = Rng::new(42);
+        assert!((0..100).all(|_| (3..=5).contains(&rng.range(3, 5))));
+        assert_eq!(rng.range(7, 7), 7);
+        // (the span doesn't fit in a u64)
+        rng.range(0, usize::MAX);
+    }
+}
@@ -58,6 +58,15 @@ If parameters are needed (for example: day 15, part 1, input data)
 $ cargo run -- run 15 one y=10 input
 \`\`\`
 
+## Run history
+
+Every `run` is recorded in `run-history.txt` (answer, duration, build profile, git commit).
+To see the trend for a day, and flag runs that are more than 10% slower than the best:
+
+\`\`\`
+$ cargo run -- history 15 one input --threshold 10
+\`\`\`
+
 ## Testing
 
 \`\`\`